@@ -0,0 +1,246 @@
+//! `cache` subcommands: inspect, prune and share the disk cache.
+
+use std::{collections::HashSet, path::PathBuf, time::Duration};
+
+use clap::Subcommand;
+
+use crate::{
+    commands::rekey::NewKey,
+    config::AppConfig,
+    core::{
+        crypto::{self, KeySource},
+        disk_cache::{CacheEntry, DiskCache},
+        engine::{ReconResult, TargetKind},
+        error::FalconError,
+    },
+};
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum CacheCommand {
+    /// Entry count, log size and age range
+    Stats,
+    /// One line per entry: key, outcome, age, size
+    List,
+    /// Cached entries for a target (prefix `domain:` for a domain)
+    Show { target: String },
+    /// Drop expired entries, or every entry older than `--older-than` (e.g. 90s, 15m, 12h, 7d)
+    Purge {
+        #[arg(long, value_parser = parse_age)]
+        older_than: Option<Duration>,
+    },
+    /// Write entries as JSON lines to stdout or `--output`
+    Export {
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Merge entries from an export file; newer entries win
+    Import { path: PathBuf },
+    /// Re-encrypt the cache under a new key, or decrypt it (`rekey` also
+    /// covers cases, watch state, evidence and saved files)
+    Rekey {
+        #[command(flatten)]
+        new_key: NewKey,
+    },
+}
+
+/// Run `command` against `cache`, returning what to print.
+pub fn run(
+    command: &CacheCommand,
+    cache: &DiskCache,
+    cfg: &AppConfig,
+) -> Result<String, FalconError> {
+    match command {
+        CacheCommand::Stats => {
+            let entries = cache.entries()?;
+            let targets: HashSet<(TargetKind, &str)> = entries
+                .iter()
+                .map(|e| (e.result.kind, e.result.target.as_str()))
+                .collect();
+            let ages = entries.iter().map(CacheEntry::age);
+            let mut out = format!(
+                "path: {}{}\nentries: {} ({} targets)\nsize: {} on disk, {} live\n",
+                cache.path().display(),
+                if cache.is_encrypted() {
+                    " (encrypted)"
+                } else {
+                    ""
+                },
+                entries.len(),
+                targets.len(),
+                format_size(cache.file_size()?),
+                format_size(entries.iter().map(|e| e.size() as u64).sum()),
+            );
+            if let (Some(oldest), Some(newest)) = (ages.clone().max(), ages.min()) {
+                out.push_str(&format!(
+                    "oldest: {}\nnewest: {}\n",
+                    format_age(oldest),
+                    format_age(newest)
+                ));
+            }
+            Ok(out)
+        }
+        CacheCommand::List => Ok(entries_table(&cache.entries()?)),
+        CacheCommand::Show { target } => {
+            let (kind, id) = TargetKind::parse_input(target);
+            let entries: Vec<CacheEntry> = cache
+                .entries()?
+                .into_iter()
+                .filter(|e| e.result.kind == kind && e.result.target == id)
+                .collect();
+            if entries.is_empty() {
+                return Ok(format!("no cache entries for {}\n", target));
+            }
+            let json = serde_json::to_string_pretty(&entries).map_err(|_| FalconError::Unknown)?;
+            Ok(format!("{}{}\n", entries_table(&entries), json))
+        }
+        CacheCommand::Purge { older_than } => {
+            // Count what we removed rather than diffing lengths: other
+            // processes may be adding entries meanwhile.
+            let purged = match older_than {
+                Some(age) => {
+                    let old: Vec<String> = cache
+                        .entries()?
+                        .into_iter()
+                        .filter(|e| e.age() >= *age)
+                        .map(|e| e.key)
+                        .collect();
+                    cache.remove(&old)?
+                }
+                None => cache.purge_expired(Duration::from_secs(cfg.cache_ttl_seconds))?,
+            };
+            cache.compact()?;
+            Ok(format!(
+                "purged {} entries, {} remaining\n",
+                purged,
+                cache.len()?
+            ))
+        }
+        CacheCommand::Export { output } => {
+            let key = KeySource::resolve(cfg.key_file.as_deref());
+            let entries = cache.entries()?;
+            let mut lines = String::new();
+            for entry in &entries {
+                lines.push_str(&serde_json::to_string(entry).map_err(|_| FalconError::Unknown)?);
+                lines.push('\n');
+            }
+            match output {
+                Some(path) => {
+                    crypto::write_file(path, lines.as_bytes(), key.as_ref())?;
+                    Ok(format!(
+                        "exported {} entries to {}\n",
+                        entries.len(),
+                        path.display()
+                    ))
+                }
+                None => Ok(lines),
+            }
+        }
+        CacheCommand::Import { path } => {
+            let key = KeySource::resolve(cfg.key_file.as_deref());
+            let data = crypto::read_input(path, key.as_ref())?;
+            let data = String::from_utf8_lossy(&data);
+            let entries = data
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .enumerate()
+                .map(|(i, line)| {
+                    serde_json::from_str::<CacheEntry>(line).map_err(|e| {
+                        FalconError::Config(format!("{}:{}: {}", path.display(), i + 1, e))
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let total = entries.len();
+            let taken = cache.import(entries)?;
+            Ok(format!(
+                "imported {} of {} entries; {} skipped (cached copy as new or newer)\n",
+                taken,
+                total,
+                total - taken
+            ))
+        }
+        CacheCommand::Rekey { new_key } => {
+            let key = new_key.resolve()?;
+            cache.rekey(key.as_ref())?;
+            Ok(format!(
+                "{} {} entries\n",
+                if key.is_some() {
+                    "re-encrypted"
+                } else {
+                    "decrypted"
+                },
+                cache.len()?
+            ))
+        }
+    }
+}
+
+fn entries_table(entries: &[CacheEntry]) -> String {
+    let width = entries.iter().map(|e| e.key.len()).max().unwrap_or(0);
+    entries
+        .iter()
+        .map(|e| {
+            format!(
+                "{:width$}  {:<12}  {:>8}  {:>9}\n",
+                e.key,
+                outcome(&e.result),
+                format_age(e.age()),
+                format_size(e.size() as u64),
+            )
+        })
+        .collect()
+}
+
+fn outcome(result: &ReconResult) -> &'static str {
+    if !result.failed.is_empty() {
+        "failed"
+    } else if !result.rate_limited.is_empty() {
+        "rate-limited"
+    } else if result.hits > 0 {
+        "hit"
+    } else if !result.restricted.is_empty() {
+        "restricted"
+    } else {
+        "miss"
+    }
+}
+
+/// Parse `90`, `90s`, `15m`, `12h` or `7d`.
+pub fn parse_age(input: &str) -> Result<Duration, String> {
+    let input = input.trim();
+    let (digits, unit) = match input.find(|c: char| !c.is_ascii_digit()) {
+        Some(at) => input.split_at(at),
+        None => (input, "s"),
+    };
+    let value: u64 = digits
+        .parse()
+        .map_err(|_| format!("invalid age `{}`", input))?;
+    let scale = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3_600,
+        "d" => 86_400,
+        _ => return Err(format!("unknown unit in `{}` (use s, m, h or d)", input)),
+    };
+    value
+        .checked_mul(scale)
+        .map(Duration::from_secs)
+        .ok_or_else(|| format!("age `{}` is too large", input))
+}
+
+fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3_599 => format!("{}m{}s", secs / 60, secs % 60),
+        3_600..=86_399 => format!("{}h{}m", secs / 3_600, secs % 3_600 / 60),
+        _ => format!("{}d{}h", secs / 86_400, secs % 86_400 / 3_600),
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    match bytes {
+        0..=1_023 => format!("{} B", bytes),
+        1_024..=1_048_575 => format!("{:.1} KiB", bytes as f64 / 1_024.0),
+        _ => format!("{:.1} MiB", bytes as f64 / 1_048_576.0),
+    }
+}
@@ -0,0 +1,123 @@
+//! `case` subcommands: create, inspect and annotate case workspaces.
+
+use std::path::Path;
+
+use clap::Subcommand;
+
+use crate::{
+    config::AppConfig,
+    core::{
+        case::{Case, CaseTarget},
+        crypto::KeySource,
+        engine::TargetKind,
+        error::FalconError,
+    },
+};
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum CaseCommand {
+    /// Create an empty case
+    New { name: String },
+    /// Show a case's targets, notes and scan history
+    Open { name: String },
+    /// List cases
+    List,
+    /// Add an analyst note to a target (prefix `domain:` for a domain)
+    Note {
+        name: String,
+        target: String,
+        text: String,
+    },
+}
+
+/// Run `command`, returning what to print.
+pub fn run(command: &CaseCommand, cfg: &AppConfig) -> Result<String, FalconError> {
+    let dir = Path::new(&cfg.case_dir);
+    let key = || KeySource::resolve(cfg.key_file.as_deref());
+    match command {
+        CaseCommand::New { name } => {
+            let case = Case::create(dir, name, key())?;
+            Ok(format!(
+                "created case {}{}\n",
+                case.name(),
+                if case.is_encrypted() {
+                    " (encrypted)"
+                } else {
+                    ""
+                }
+            ))
+        }
+        CaseCommand::Open { name } => Ok(summary(&Case::open(dir, name, key())?)),
+        CaseCommand::List => {
+            let cases = Case::list(dir)?;
+            if cases.is_empty() {
+                return Ok(format!("no cases in {}\n", dir.display()));
+            }
+            Ok(cases
+                .iter()
+                .map(|c| {
+                    format!(
+                        "{}  created {}  updated {}  {} B{}\n",
+                        c.name,
+                        c.created_at.format("%Y-%m-%d %H:%M"),
+                        c.modified
+                            .map_or("?".to_string(), |m| m.format("%Y-%m-%d %H:%M").to_string()),
+                        c.size,
+                        if c.encrypted { "  encrypted" } else { "" }
+                    )
+                })
+                .collect())
+        }
+        CaseCommand::Note { name, target, text } => {
+            let mut case = Case::open(dir, name, key())?;
+            let (kind, id) = TargetKind::parse_input(target);
+            case.add_note(kind, &id, text)?;
+            Ok(format!("noted on {} in case {}\n", target, case.name()))
+        }
+    }
+}
+
+fn summary(case: &Case) -> String {
+    let scans: usize = case.targets().iter().map(|t| t.scans.len()).sum();
+    let mut out = format!(
+        "case: {} (created {})\ntargets: {}, scans: {}\n",
+        case.name(),
+        case.created_at().format("%Y-%m-%d %H:%M UTC"),
+        case.targets().len(),
+        scans
+    );
+    for target in case.targets() {
+        out.push_str(&target_line(target));
+        for note in &target.notes {
+            out.push_str(&format!(
+                "    note {}: {}\n",
+                note.at.format("%Y-%m-%d %H:%M"),
+                note.text
+            ));
+        }
+    }
+    out
+}
+
+fn target_line(target: &CaseTarget) -> String {
+    let name = match target.kind {
+        TargetKind::Username => target.id.clone(),
+        TargetKind::Domain => format!("domain:{}", target.id),
+    };
+    let label = target
+        .label
+        .as_ref()
+        .map_or(String::new(), |l| format!(" [{}]", l));
+    match target.last_scan() {
+        Some(last) => format!(
+            "  {}{}  {} scans, last {}: {} hits ({})\n",
+            name,
+            label,
+            target.scans.len(),
+            last.at.format("%Y-%m-%d %H:%M"),
+            last.result.hits,
+            last.result.platforms.join(", ")
+        ),
+        None => format!("  {}{}  not scanned\n", name, label),
+    }
+}
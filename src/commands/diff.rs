@@ -0,0 +1,146 @@
+//! `diff` command: compare two scans of a target.
+
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+
+use crate::{
+    config::AppConfig,
+    core::{
+        case::Case,
+        crypto::{self, KeySource},
+        engine::{Engine, ReconResult, TargetKind},
+        error::FalconError,
+    },
+    export::envelope::parse_results,
+    modules::diff::{diff, ScanDiff},
+};
+
+#[derive(Args, Debug, Clone)]
+pub struct DiffArgs {
+    /// Target to diff (prefix `domain:` for a domain). Without `--case`, the
+    /// cached result is compared with a fresh scan.
+    pub target: Option<String>,
+    /// Compare scans stored in this case
+    #[arg(long = "case")]
+    pub case_name: Option<String>,
+    /// 1-based scan number in the case to diff from (default: second to last)
+    #[arg(long, requires = "case_name")]
+    pub from: Option<usize>,
+    /// 1-based scan number in the case to diff to (default: last)
+    #[arg(long, requires = "case_name")]
+    pub to: Option<usize>,
+    /// Earlier result file (JSON output of a headless run)
+    #[arg(long, requires = "after", conflicts_with_all = ["case_name", "target"])]
+    pub before: Option<PathBuf>,
+    /// Later result file
+    #[arg(long, requires = "before")]
+    pub after: Option<PathBuf>,
+    /// Print JSON instead of text
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Resolve the two scans named by `args` and diff them.
+pub async fn run(args: &DiffArgs, engine: &Engine, cfg: &AppConfig) -> Result<String, FalconError> {
+    let key = KeySource::resolve(cfg.key_file.as_deref());
+    let diff = match (&args.before, &args.after) {
+        (Some(before), Some(after)) => diff(
+            &read_result(before, key.as_ref())?,
+            &read_result(after, key.as_ref())?,
+            None,
+            None,
+        )?,
+        _ => {
+            let target = args.target.as_deref().ok_or_else(|| {
+                FalconError::Config("diff needs a target, or --before and --after".into())
+            })?;
+            let (kind, id) = TargetKind::parse_input(target);
+            match &args.case_name {
+                Some(name) => {
+                    let case = Case::open(Path::new(&cfg.case_dir), name, key)?;
+                    from_case(&case, kind, &id, args.from, args.to)?
+                }
+                None => against_cache(engine, kind, &id).await?,
+            }
+        }
+    };
+    if args.json {
+        serde_json::to_string_pretty(&diff)
+            .map(|json| json + "\n")
+            .map_err(|_| FalconError::Unknown)
+    } else {
+        Ok(diff.to_text())
+    }
+}
+
+/// Diff the cached result for a target against a fresh scan. Only providers
+/// with a cached outcome are compared: failures are never cached and each
+/// outcome expires on its own TTL, so a provider missing from the cache says
+/// nothing about the account.
+pub async fn against_cache(
+    engine: &Engine,
+    kind: TargetKind,
+    id: &str,
+) -> Result<ScanDiff, FalconError> {
+    let cached = engine
+        .cached_result(kind, id)
+        .ok_or_else(|| FalconError::Config(format!("nothing cached for {}", id)))?;
+    let fresh = engine.scan_target(kind, id, false).await?;
+    let mut diff = diff(&cached, &fresh, None, Some(chrono::Utc::now()))?;
+    diff.changes
+        .retain(|change| cached.cached.contains(&change.provider));
+    diff.unchanged
+        .retain(|provider| cached.cached.contains(provider));
+    Ok(diff)
+}
+
+/// Diff scans `from` and `to` (1-based; default the last two) of a case target.
+pub fn from_case(
+    case: &Case,
+    kind: TargetKind,
+    id: &str,
+    from: Option<usize>,
+    to: Option<usize>,
+) -> Result<ScanDiff, FalconError> {
+    let target = case
+        .target(kind, id)
+        .ok_or_else(|| FalconError::Config(format!("{} is not in case {}", id, case.name())))?;
+    let count = target.scans.len();
+    if count < 2 && (from.is_none() || to.is_none()) {
+        return Err(FalconError::Config(format!(
+            "{} has {} scan(s) in case {}; need two to diff",
+            id,
+            count,
+            case.name()
+        )));
+    }
+    let pick = |n: usize| {
+        n.checked_sub(1)
+            .and_then(|i| target.scans.get(i))
+            .ok_or_else(|| FalconError::Config(format!("no scan #{} (1..={})", n, count)))
+    };
+    let before = pick(from.unwrap_or(count - 1))?;
+    let after = pick(to.unwrap_or(count))?;
+    diff(
+        &before.result,
+        &after.result,
+        Some(before.at),
+        Some(after.at),
+    )
+}
+
+fn read_result(path: &Path, key: Option<&KeySource>) -> Result<ReconResult, FalconError> {
+    let data = crypto::read_input(path, key)?;
+    let mut results = parse_results(&data).map_err(|e| {
+        FalconError::Config(format!("{}: not a scan result: {}", path.display(), e))
+    })?;
+    if results.len() != 1 {
+        return Err(FalconError::Config(format!(
+            "{}: holds {} results; diff needs exactly one",
+            path.display(),
+            results.len()
+        )));
+    }
+    Ok(results.remove(0))
+}
@@ -0,0 +1,64 @@
+//! `evidence` subcommands: inspect and maintain the evidence vault.
+
+use std::path::PathBuf;
+
+use clap::Subcommand;
+
+use crate::core::{crypto, error::FalconError, evidence::EvidenceVault};
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum EvidenceCommand {
+    /// Hashes of every stored capture
+    List,
+    /// Print a capture after checking its hash
+    Show {
+        sha256: String,
+        /// Write the body snapshot to this file (sealed when a key is set)
+        #[arg(long)]
+        body: Option<PathBuf>,
+    },
+    /// Re-hash every capture and report any that were altered
+    Verify,
+    /// Apply the retention and size limits now
+    Prune,
+}
+
+/// Run `command` against `vault`, returning what to print.
+pub fn run(command: &EvidenceCommand, vault: &EvidenceVault) -> Result<String, FalconError> {
+    match command {
+        EvidenceCommand::List => Ok(vault
+            .list()?
+            .into_iter()
+            .map(|hash| format!("{}\n", hash))
+            .collect()),
+        EvidenceCommand::Show { sha256, body } => {
+            let record = vault.load(sha256)?;
+            if let Some(path) = body {
+                crypto::write_file(path, &record.body()?, vault.key())?;
+            }
+            let json = serde_json::to_string_pretty(&record).map_err(|_| FalconError::Unknown)?;
+            Ok(format!("{}\n", json))
+        }
+        EvidenceCommand::Verify => {
+            let report = vault.verify()?;
+            let mut out = format!(
+                "{} capture(s) intact, {} failed\n",
+                report.ok,
+                report.bad.len()
+            );
+            for (hash, reason) in &report.bad {
+                out.push_str(&format!("{}  {}\n", hash, reason));
+            }
+            if report.bad.is_empty() {
+                Ok(out)
+            } else {
+                Err(FalconError::Crypto(out.trim_end().to_string()))
+            }
+        }
+        EvidenceCommand::Prune => Ok(format!(
+            "pruned {} capture(s) from {}\n",
+            vault.prune()?,
+            vault.dir().display()
+        )),
+    }
+}
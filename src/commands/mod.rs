@@ -0,0 +1,8 @@
+//! Non-scan subcommands.
+
+pub mod cache;
+pub mod case;
+pub mod diff;
+pub mod evidence;
+pub mod rekey;
+pub mod watch;
@@ -0,0 +1,154 @@
+//! `rekey` command: move every sealed artifact to a new key, or decrypt it.
+
+use std::path::{Path, PathBuf};
+
+use clap::{ArgGroup, Args};
+
+use crate::{
+    config::AppConfig,
+    core::{
+        case::Case,
+        crypto::{self, KeySource, PASSPHRASE_ENV},
+        disk_cache::DiskCache,
+        error::FalconError,
+        evidence::EvidenceVault,
+    },
+    modules::watch::WatchState,
+};
+
+/// The key to move to.
+#[derive(Args, Debug, Clone)]
+#[command(group(
+    ArgGroup::new("new_key")
+        .required(true)
+        .args(["new_key_file", "new_passphrase_env", "decrypt"])
+))]
+pub struct NewKey {
+    /// Key file for the new key
+    #[arg(long)]
+    pub new_key_file: Option<PathBuf>,
+    /// Environment variable holding the new passphrase
+    #[arg(long)]
+    pub new_passphrase_env: Option<String>,
+    /// Store everything in plaintext
+    #[arg(long)]
+    pub decrypt: bool,
+}
+
+impl NewKey {
+    /// The new key, or `None` for `--decrypt`.
+    pub fn resolve(&self) -> Result<Option<KeySource>, FalconError> {
+        match (&self.new_key_file, &self.new_passphrase_env) {
+            (Some(path), _) => Ok(Some(KeySource::KeyFile(path.clone()))),
+            (None, Some(var)) => KeySource::from_env(var)
+                .map(Some)
+                .ok_or_else(|| FalconError::Config(format!("{} is not set", var))),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct RekeyArgs {
+    #[command(flatten)]
+    pub new_key: NewKey,
+    /// Files written with `--output` or `cache export --output` to rekey as
+    /// well; plaintext ones are encrypted
+    pub files: Vec<PathBuf>,
+}
+
+/// Rekey the disk cache, every case, the watch state, the evidence vault and
+/// `args.files` from the configured key to the new one. Artifacts already
+/// under the new key are skipped, so an interrupted run can be repeated.
+pub fn run(args: &RekeyArgs, cfg: &AppConfig) -> Result<String, FalconError> {
+    let old = KeySource::resolve(cfg.key_file.as_deref());
+    let new = args.new_key.resolve()?;
+    let verb = if new.is_some() {
+        "re-encrypted"
+    } else {
+        "decrypted"
+    };
+    let mut out = String::new();
+
+    let cache_path = Path::new(&cfg.disk_cache_path);
+    if cache_path.exists() {
+        match open_either(&old, &new, |key| DiskCache::open(cache_path, key))? {
+            Some(cache) => {
+                cache.rekey(new.as_ref())?;
+                out.push_str(&format!(
+                    "{} cache {} ({} entries)\n",
+                    verb,
+                    cache_path.display(),
+                    cache.len()?
+                ));
+            }
+            None => out.push_str(&already(cache_path)),
+        }
+    }
+
+    let case_dir = Path::new(&cfg.case_dir);
+    for summary in Case::list(case_dir)? {
+        match open_either(&old, &new, |key| Case::open(case_dir, &summary.name, key))? {
+            Some(mut case) => {
+                case.rekey(new.as_ref())?;
+                out.push_str(&format!("{} case {}\n", verb, summary.name));
+            }
+            None => out.push_str(&format!(
+                "case {} is already under the new key\n",
+                summary.name
+            )),
+        }
+    }
+
+    let watch_path = Path::new(&cfg.watch_state_path);
+    if watch_path.exists() {
+        match open_either(&old, &new, |key| WatchState::load(watch_path, key.as_ref()))? {
+            Some(state) => {
+                state.save(watch_path, new.as_ref())?;
+                out.push_str(&format!("{} watch state {}\n", verb, watch_path.display()));
+            }
+            None => out.push_str(&already(watch_path)),
+        }
+    }
+
+    let vault = EvidenceVault::new(cfg, old.clone());
+    let captures = vault.rekey(new.as_ref())?;
+    if captures > 0 {
+        out.push_str(&format!("{} {} evidence capture(s)\n", verb, captures));
+    }
+
+    for path in &args.files {
+        if crypto::rekey_file(path, old.as_ref(), new.as_ref())? {
+            out.push_str(&format!("{} {}\n", verb, path.display()));
+        } else {
+            out.push_str(&already(path));
+        }
+    }
+    if new.is_some() {
+        out.push_str(&format!(
+            "now point --key-file or {} at the new key\n",
+            PASSPHRASE_ENV
+        ));
+    }
+    Ok(out)
+}
+
+/// Open with the old key; `None` when that fails but the new key works.
+fn open_either<T>(
+    old: &Option<KeySource>,
+    new: &Option<KeySource>,
+    open: impl Fn(Option<KeySource>) -> Result<T, FalconError>,
+) -> Result<Option<T>, FalconError> {
+    match open(old.clone()) {
+        Ok(opened) => Ok(Some(opened)),
+        Err(e) if new.is_some() && open(new.clone()).is_ok() => {
+            tracing::debug!("skipping, already under the new key: {}", e);
+            Ok(None)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+fn already(path: &Path) -> String {
+    format!("{} is already under the new key\n", path.display())
+}
@@ -0,0 +1,101 @@
+//! `watch` command: rescan targets on a schedule and alert on changes.
+
+use std::{path::PathBuf, time::Duration};
+
+use chrono::Utc;
+use clap::{ArgGroup, Args};
+
+use crate::{
+    commands::cache::parse_age,
+    config::AppConfig,
+    core::{
+        crypto::KeySource,
+        engine::{Engine, TargetKind},
+        error::FalconError,
+    },
+    export::siem::SiemSinks,
+    modules::watch::{AlertSinks, Schedule, Watcher},
+};
+
+#[derive(Args, Debug, Clone)]
+#[command(group(ArgGroup::new("schedule").required(true).args(["interval", "cron"])))]
+pub struct WatchArgs {
+    /// Targets to watch (prefix `domain:` for a domain)
+    #[arg(required = true)]
+    pub targets: Vec<String>,
+    /// Rescan every interval (e.g. 30m, 6h, 1d)
+    #[arg(long, value_parser = parse_age)]
+    pub interval: Option<Duration>,
+    /// Rescan on a cron schedule (5 or 6 fields, UTC), e.g. "0 */6 * * *"
+    #[arg(long)]
+    pub cron: Option<String>,
+    /// Append alerts as NDJSON to this file (plaintext, even with a key)
+    #[arg(long)]
+    pub alert_log: Option<PathBuf>,
+    /// POST each alert as JSON to this URL (overrides config `webhook_url`)
+    #[arg(long)]
+    pub webhook: Option<String>,
+    /// Do not print alerts to stdout
+    #[arg(long)]
+    pub quiet: bool,
+    /// Schedule state file (overrides config `watch_state_path`)
+    #[arg(long)]
+    pub state: Option<PathBuf>,
+    /// Scan whatever is due once and exit (for running from system cron)
+    #[arg(long)]
+    pub once: bool,
+}
+
+pub async fn run(args: &WatchArgs, engine: &Engine, cfg: &AppConfig) -> Result<(), FalconError> {
+    let schedule = match (&args.interval, &args.cron) {
+        (Some(interval), _) => Schedule::Every(*interval),
+        (None, Some(expr)) => Schedule::cron(expr)?,
+        (None, None) => unreachable!("clap requires --interval or --cron"),
+    };
+    let targets = args
+        .targets
+        .iter()
+        .map(|t| TargetKind::parse_input(t))
+        .collect();
+    let sinks = AlertSinks {
+        stdout: !args.quiet,
+        log: args.alert_log.clone(),
+        webhook: args.webhook.clone().or_else(|| cfg.webhook_url.clone()),
+    };
+    let state_path = args
+        .state
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(&cfg.watch_state_path));
+    let key = KeySource::resolve(cfg.key_file.as_deref());
+    if let (Some(path), Some(_)) = (&sinks.log, &key) {
+        tracing::warn!(
+            "alert log {} is written in plaintext; only the watch state is encrypted",
+            path.display()
+        );
+    }
+    let mut watcher = Watcher::new(schedule, targets, sinks, state_path, key)?
+        .with_siem(SiemSinks::from_config(cfg));
+
+    loop {
+        let now = Utc::now();
+        if watcher.next_due().is_some_and(|due| due <= now) {
+            let alerts = watcher.tick(engine, now).await?;
+            tracing::info!("watch: scanned due targets, {} alerts", alerts.len());
+        }
+        if args.once {
+            return Ok(());
+        }
+        let Some(due) = watcher.next_due() else {
+            return Ok(());
+        };
+        let wait = (due - Utc::now())
+            .to_std()
+            .unwrap_or_default()
+            .max(Duration::from_secs(1));
+        tracing::info!("watch: next run at {}", due.format("%Y-%m-%d %H:%M:%S UTC"));
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+    }
+}
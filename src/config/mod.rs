@@ -1,27 +1,188 @@
 use std::{fs, path::Path};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 use crate::core::error::FalconError;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProviderConfig {
     pub name: String,
     pub enabled: bool,
     pub base_url: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+/// Elasticsearch `_bulk` sink.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElasticsearchConfig {
+    /// Cluster URL; results are POSTed to `<url>/_bulk`.
+    pub url: String,
+    #[serde(default = "default_siem_index")]
+    pub index: String,
+    /// Sent as `Authorization: ApiKey <api_key>`. Never serialized, so it
+    /// stays out of `config_hash`.
+    #[serde(default, skip_serializing)]
+    pub api_key: Option<String>,
+}
+
+/// Splunk HTTP Event Collector sink.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplunkConfig {
+    /// HEC URL; events are POSTed to `<url>/services/collector/event`.
+    pub url: String,
+    /// HEC token; never serialized, like the other secrets.
+    #[serde(skip_serializing)]
+    pub token: String,
+    /// Index to write to; the token's default index when unset.
+    #[serde(default)]
+    pub index: Option<String>,
+    #[serde(default = "default_sourcetype")]
+    pub sourcetype: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
     pub timeout_ms: u64,
     pub max_concurrent_requests: usize,
+    /// How long a provider hit stays cached; 0 turns caching off entirely.
     pub cache_ttl_seconds: u64,
+    /// How long a confirmed miss (not found) stays cached; defaults to
+    /// `cache_ttl_seconds`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub negative_cache_ttl_seconds: Option<u64>,
+    /// How long a restricted (401/403) outcome stays cached; defaults to
+    /// `cache_ttl_seconds`. Failures, rate limits and timeouts are never
+    /// cached.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restricted_cache_ttl_seconds: Option<u64>,
     pub user_agent: String,
     pub disk_cache_enabled: bool,
     pub disk_cache_path: String,
+    /// Key file for encrypting the disk cache and saved files. Without one,
+    /// `BLOODY_FALCON_PASSPHRASE` is used when set.
+    #[serde(default)]
+    pub key_file: Option<String>,
+    /// Directory holding case workspaces.
+    #[serde(default = "default_case_dir")]
+    pub case_dir: String,
+    /// Schedule state for `watch`, so schedules survive restarts.
+    #[serde(default = "default_watch_state_path")]
+    pub watch_state_path: String,
+    /// Webhook that `watch` POSTs alerts to. Often carries a secret in the
+    /// path, so it is never serialized.
+    #[serde(default, skip_serializing)]
+    pub webhook_url: Option<String>,
+    /// HTML report template; the built-in one is used when the file is missing.
+    #[serde(default = "default_report_template")]
+    pub report_template: String,
+    /// Keep hashed captures of what providers returned for hits (`--evidence`).
+    #[serde(default)]
+    pub evidence_enabled: bool,
+    /// Directory of the content-addressed evidence store.
+    #[serde(default = "default_evidence_dir")]
+    pub evidence_dir: String,
+    /// Largest response body snapshot kept per capture.
+    #[serde(default = "default_evidence_max_body_bytes")]
+    pub evidence_max_body_bytes: usize,
+    /// Captures older than this are pruned; 0 keeps them until the size cap.
+    #[serde(default = "default_evidence_retention_days")]
+    pub evidence_retention_days: u64,
+    /// Total size cap for the store; the oldest captures go first.
+    #[serde(default = "default_evidence_max_total_mb")]
+    pub evidence_max_total_mb: u64,
+    /// Post headless and watch results to Elasticsearch.
+    #[serde(default)]
+    pub elasticsearch: Option<ElasticsearchConfig>,
+    /// Post headless and watch results to Splunk HEC.
+    #[serde(default)]
+    pub splunk: Option<SplunkConfig>,
+    /// Results per SIEM request.
+    #[serde(default = "default_siem_batch_size")]
+    pub siem_batch_size: usize,
+    /// Retries for a SIEM batch after a network error, 429 or 5xx.
+    #[serde(default = "default_siem_retries")]
+    pub siem_retries: u32,
+    /// RDAP lookup URL for domain targets; `{domain}` is substituted.
+    #[serde(default = "default_rdap_url")]
+    pub rdap_url: String,
+    /// Certificate-transparency search URL (crt.sh JSON shape); `{domain}` is substituted.
+    #[serde(default = "default_ct_url")]
+    pub ct_url: String,
     pub providers: Vec<ProviderConfig>,
 }
 
+impl Default for AppConfig {
+    fn default() -> Self {
+        default_config()
+    }
+}
+
+impl AppConfig {
+    /// `sha256:` digest of the effective configuration (after flags and
+    /// provider filters), so outputs can be tied to the settings behind them.
+    /// Credentials are not serialized and so are not part of it.
+    pub fn digest(&self) -> String {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        let hash: String = Sha256::digest(&json)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        format!("sha256:{}", hash)
+    }
+}
+
+fn default_case_dir() -> String {
+    "data/cases".to_string()
+}
+
+fn default_watch_state_path() -> String {
+    "data/watch.json".to_string()
+}
+
+fn default_report_template() -> String {
+    "assets/templates/report.html".to_string()
+}
+
+fn default_evidence_dir() -> String {
+    "data/evidence".to_string()
+}
+
+fn default_evidence_max_body_bytes() -> usize {
+    64 * 1024
+}
+
+fn default_evidence_retention_days() -> u64 {
+    30
+}
+
+fn default_evidence_max_total_mb() -> u64 {
+    100
+}
+
+fn default_siem_index() -> String {
+    "bloody-f4lcon".to_string()
+}
+
+fn default_sourcetype() -> String {
+    "bloody_f4lcon:result".to_string()
+}
+
+fn default_siem_batch_size() -> usize {
+    100
+}
+
+fn default_siem_retries() -> u32 {
+    3
+}
+
+fn default_rdap_url() -> String {
+    "https://rdap.org/domain/{domain}".to_string()
+}
+
+fn default_ct_url() -> String {
+    "https://crt.sh/?q=%25.{domain}&output=json".to_string()
+}
+
 pub fn load_config(path: Option<&str>) -> Result<AppConfig, FalconError> {
     let default_path = Path::new("config/bloodyf4lcon.toml");
     let path = path.map(Path::new).unwrap_or(default_path);
@@ -53,10 +214,28 @@ fn default_config() -> AppConfig {
         timeout_ms: 5_000,
         max_concurrent_requests: 5,
         cache_ttl_seconds: 600,
+        negative_cache_ttl_seconds: None,
+        restricted_cache_ttl_seconds: None,
         user_agent: "bloody-f4lcon/1.0 (+https://github.com/ind4skylivey/bloody-f4lcon)"
             .to_string(),
         disk_cache_enabled: false,
-        disk_cache_path: "data/cache.json".to_string(),
+        disk_cache_path: "data/cache.log".to_string(),
+        key_file: None,
+        case_dir: default_case_dir(),
+        watch_state_path: default_watch_state_path(),
+        webhook_url: None,
+        report_template: default_report_template(),
+        evidence_enabled: false,
+        evidence_dir: default_evidence_dir(),
+        evidence_max_body_bytes: default_evidence_max_body_bytes(),
+        evidence_retention_days: default_evidence_retention_days(),
+        evidence_max_total_mb: default_evidence_max_total_mb(),
+        elasticsearch: None,
+        splunk: None,
+        siem_batch_size: default_siem_batch_size(),
+        siem_retries: default_siem_retries(),
+        rdap_url: default_rdap_url(),
+        ct_url: default_ct_url(),
         providers: vec![
             ProviderConfig {
                 name: "github".to_string(),
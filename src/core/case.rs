@@ -0,0 +1,457 @@
+//! Case workspaces: a named, append-only record of an investigation.
+//!
+//! A case lives in one JSON-lines file under the case directory. The first
+//! line is a header; every following line adds a target, a note or a scan
+//! result, so nothing is ever overwritten and the scan history of each target
+//! is kept in order. With a key the records are sealed line by line, as in
+//! the disk cache, each bound to the header. A record that cannot be read is
+//! an error; only a torn last line left by a crash is dropped.
+
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::{
+    crypto::{KeyParams, KeySource, Sealer, PASSPHRASE_ENV},
+    engine::{ReconResult, TargetKind},
+    error::FalconError,
+};
+
+const FORMAT: &str = "bloody-falcon-case";
+const VERSION: u32 = 1;
+const EXTENSION: &str = "jsonl";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Note {
+    pub at: DateTime<Utc>,
+    pub text: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ScanRecord {
+    pub at: DateTime<Utc>,
+    pub result: ReconResult,
+}
+
+#[derive(Clone, Debug)]
+pub struct CaseTarget {
+    pub kind: TargetKind,
+    pub id: String,
+    pub label: Option<String>,
+    pub notes: Vec<Note>,
+    /// Every scan of this target, oldest first.
+    pub scans: Vec<ScanRecord>,
+}
+
+impl CaseTarget {
+    pub fn last_scan(&self) -> Option<&ScanRecord> {
+        self.scans.last()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Header {
+    format: String,
+    version: u32,
+    name: String,
+    created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    encryption: Option<KeyParams>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "op", rename_all = "lowercase")]
+enum Record {
+    Target {
+        kind: TargetKind,
+        id: String,
+        #[serde(default)]
+        label: Option<String>,
+    },
+    Note {
+        kind: TargetKind,
+        id: String,
+        note: Note,
+    },
+    Scan {
+        at: DateTime<Utc>,
+        result: Box<ReconResult>,
+    },
+}
+
+/// Name, creation time and size of a case on disk.
+#[derive(Clone, Debug)]
+pub struct CaseSummary {
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub modified: Option<DateTime<Utc>>,
+    pub size: u64,
+    pub encrypted: bool,
+}
+
+pub struct Case {
+    name: String,
+    path: PathBuf,
+    created_at: DateTime<Utc>,
+    targets: Vec<CaseTarget>,
+    sealer: Option<Sealer>,
+    /// The header, authenticated by every sealed record.
+    aad: Vec<u8>,
+    /// Key for a plaintext case opened with one; applied on the next write.
+    pending_key: Option<KeySource>,
+}
+
+impl Case {
+    /// Create a new, empty case; fails if one with this name exists.
+    pub fn create(dir: &Path, name: &str, key: Option<KeySource>) -> Result<Self, FalconError> {
+        let path = case_path(dir, name)?;
+        if path.exists() {
+            return Err(FalconError::Config(format!("case {} already exists", name)));
+        }
+        fs::create_dir_all(dir).map_err(|e| case_error(dir, e))?;
+        let mut case = Self {
+            name: name.to_string(),
+            path,
+            created_at: Utc::now(),
+            targets: Vec::new(),
+            sealer: key.as_ref().map(KeySource::create).transpose()?,
+            aad: Vec::new(),
+            pending_key: None,
+        };
+        case.rewrite(&[])?;
+        Ok(case)
+    }
+
+    /// Open an existing case. An encrypted case needs its key; a plaintext
+    /// case opened with a key is read as-is and encrypted by the next write.
+    pub fn open(dir: &Path, name: &str, key: Option<KeySource>) -> Result<Self, FalconError> {
+        let path = case_path(dir, name)?;
+        if !path.exists() {
+            return Err(FalconError::Config(format!(
+                "no case named {}; create it with `case new {}`",
+                name, name
+            )));
+        }
+        let data = fs::read_to_string(&path).map_err(|e| case_error(&path, e))?;
+        // Everything after the last newline is a torn append; cut it off so
+        // the next append starts on a fresh line.
+        let complete = data.rfind('\n').map_or(0, |end| end + 1);
+        if complete > 0 && complete < data.len() {
+            tracing::warn!(
+                "dropping torn last record of case {} ({} bytes)",
+                name,
+                data.len() - complete
+            );
+            OpenOptions::new()
+                .write(true)
+                .open(&path)
+                .and_then(|file| file.set_len(complete as u64))
+                .map_err(|e| case_error(&path, e))?;
+        }
+        let mut lines = data[..complete].lines();
+        let header = parse_header(lines.next().unwrap_or_default(), &path)?;
+        let aad = header_aad(&header)?;
+        let sealer = match (&header.encryption, &key) {
+            (None, _) => None,
+            (Some(params), Some(key)) => Some(key.unlock(params).map_err(|e| match e {
+                FalconError::Crypto(msg) => {
+                    FalconError::Crypto(format!("{}: {}", path.display(), msg))
+                }
+                other => other,
+            })?),
+            (Some(_), None) => {
+                return Err(FalconError::Crypto(format!(
+                    "case {} is encrypted; pass --key-file or set {}",
+                    name, PASSPHRASE_ENV
+                )))
+            }
+        };
+        let mut case = Self {
+            name: header.name,
+            path,
+            created_at: header.created_at,
+            targets: Vec::new(),
+            sealer,
+            aad,
+            pending_key: None,
+        };
+        let records = case.decode_all(lines)?;
+        if let (Some(key), None) = (key, &case.sealer) {
+            tracing::warn!(
+                "case {} is not encrypted; it will be encrypted on the next change",
+                case.name
+            );
+            case.pending_key = Some(key);
+        }
+        for record in records {
+            case.apply(record);
+        }
+        Ok(case)
+    }
+
+    /// Cases in `dir`, sorted by name.
+    pub fn list(dir: &Path) -> Result<Vec<CaseSummary>, FalconError> {
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut cases = Vec::new();
+        for entry in fs::read_dir(dir).map_err(|e| case_error(dir, e))? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(EXTENSION) {
+                continue;
+            }
+            let data = fs::read_to_string(&path).map_err(|e| case_error(&path, e))?;
+            let Ok(header) = parse_header(data.lines().next().unwrap_or_default(), &path) else {
+                continue;
+            };
+            let meta = fs::metadata(&path)?;
+            cases.push(CaseSummary {
+                name: header.name,
+                created_at: header.created_at,
+                modified: meta.modified().ok().map(DateTime::<Utc>::from),
+                size: meta.len(),
+                encrypted: header.encryption.is_some(),
+            });
+        }
+        cases.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(cases)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.sealer.is_some()
+    }
+
+    pub fn targets(&self) -> &[CaseTarget] {
+        &self.targets
+    }
+
+    pub fn target(&self, kind: TargetKind, id: &str) -> Option<&CaseTarget> {
+        self.targets.iter().find(|t| t.kind == kind && t.id == id)
+    }
+
+    /// Add a target, or update its label when one is given.
+    pub fn add_target(
+        &mut self,
+        kind: TargetKind,
+        id: &str,
+        label: Option<String>,
+    ) -> Result<(), FalconError> {
+        if let Some(existing) = self.target(kind, id) {
+            if label.is_none() || existing.label == label {
+                return Ok(());
+            }
+        }
+        self.append(Record::Target {
+            kind,
+            id: id.to_string(),
+            label,
+        })
+    }
+
+    pub fn add_note(&mut self, kind: TargetKind, id: &str, text: &str) -> Result<(), FalconError> {
+        self.add_target(kind, id, None)?;
+        self.append(Record::Note {
+            kind,
+            id: id.to_string(),
+            note: Note {
+                at: Utc::now(),
+                text: text.to_string(),
+            },
+        })
+    }
+
+    /// Append a scan result to its target's history.
+    pub fn record_scan(&mut self, result: &ReconResult) -> Result<(), FalconError> {
+        self.add_target(result.kind, &result.target, None)?;
+        self.append(Record::Scan {
+            at: Utc::now(),
+            result: Box::new(result.clone()),
+        })
+    }
+
+    fn apply(&mut self, record: Record) {
+        let (kind, id) = match &record {
+            Record::Target { kind, id, .. } | Record::Note { kind, id, .. } => (*kind, id.clone()),
+            Record::Scan { result, .. } => (result.kind, result.target.clone()),
+        };
+        let idx = match self
+            .targets
+            .iter()
+            .position(|t| t.kind == kind && t.id == id)
+        {
+            Some(idx) => idx,
+            None => {
+                self.targets.push(CaseTarget {
+                    kind,
+                    id,
+                    label: None,
+                    notes: Vec::new(),
+                    scans: Vec::new(),
+                });
+                self.targets.len() - 1
+            }
+        };
+        let target = &mut self.targets[idx];
+        match record {
+            Record::Target { label, .. } => {
+                if label.is_some() {
+                    target.label = label;
+                }
+            }
+            Record::Note { note, .. } => target.notes.push(note),
+            Record::Scan { at, result } => target.scans.push(ScanRecord {
+                at,
+                result: *result,
+            }),
+        }
+    }
+
+    /// Rewrite the case under `key`, or in plaintext when `None`.
+    pub fn rekey(&mut self, key: Option<&KeySource>) -> Result<(), FalconError> {
+        let records = self.read_records()?;
+        self.sealer = key.map(KeySource::create).transpose()?;
+        self.pending_key = None;
+        self.rewrite(&records)
+    }
+
+    fn append(&mut self, record: Record) -> Result<(), FalconError> {
+        if let Some(key) = self.pending_key.take() {
+            let records = self.read_records()?;
+            self.sealer = Some(key.create()?);
+            self.rewrite(&records)?;
+            tracing::info!("encrypted case {}", self.name);
+        }
+        let line = self.encode(&record)?;
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| case_error(&self.path, e))?;
+        file.write_all(&line)?;
+        file.sync_data()?;
+        self.apply(record);
+        Ok(())
+    }
+
+    /// Write the header and `records` via temp file + rename.
+    fn rewrite(&mut self, records: &[Record]) -> Result<(), FalconError> {
+        let header = Header {
+            format: FORMAT.to_string(),
+            version: VERSION,
+            name: self.name.clone(),
+            created_at: self.created_at,
+            encryption: self.sealer.as_ref().map(|s| s.params().clone()),
+        };
+        self.aad = header_aad(&header)?;
+        let mut buf = self.aad.clone();
+        buf.push(b'\n');
+        for record in records {
+            buf.extend(self.encode(record)?);
+        }
+        let tmp = self.path.with_extension("tmp");
+        let mut file = fs::File::create(&tmp).map_err(|e| case_error(&tmp, e))?;
+        file.write_all(&buf)?;
+        file.sync_all()?;
+        fs::rename(&tmp, &self.path).map_err(|e| case_error(&self.path, e))
+    }
+
+    fn encode(&self, record: &Record) -> Result<Vec<u8>, FalconError> {
+        let json = serde_json::to_vec(record).map_err(|_| FalconError::Unknown)?;
+        let mut line = match &self.sealer {
+            Some(sealer) => sealer.seal_bound(&json, &self.aad).into_bytes(),
+            None => json,
+        };
+        line.push(b'\n');
+        Ok(line)
+    }
+
+    /// Every complete record currently in the file.
+    fn read_records(&self) -> Result<Vec<Record>, FalconError> {
+        let data = fs::read_to_string(&self.path).map_err(|e| case_error(&self.path, e))?;
+        let complete = data.rfind('\n').map_or(0, |end| end + 1);
+        self.decode_all(data[..complete].lines().skip(1))
+    }
+
+    /// Decode the record lines following the header.
+    fn decode_all<'a>(
+        &self,
+        lines: impl Iterator<Item = &'a str>,
+    ) -> Result<Vec<Record>, FalconError> {
+        let mut records = Vec::new();
+        for (n, line) in lines.enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+            let record = self.decode(line).map_err(|e| {
+                let msg = format!(
+                    "{}: record {} is unreadable: {}",
+                    self.path.display(),
+                    n + 2,
+                    e
+                );
+                match self.sealer {
+                    Some(_) => FalconError::Crypto(msg),
+                    None => FalconError::Config(msg),
+                }
+            })?;
+            records.push(record);
+        }
+        Ok(records)
+    }
+
+    fn decode(&self, line: &str) -> Result<Record, String> {
+        match &self.sealer {
+            Some(sealer) => {
+                let plain = sealer
+                    .open_bound(line, &self.aad)
+                    .map_err(|e| e.to_string())?;
+                serde_json::from_slice(&plain).map_err(|e| e.to_string())
+            }
+            None => serde_json::from_str(line).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+/// File for case `name`; names are limited to letters, digits, `-`, `_` and `.`.
+fn case_path(dir: &Path, name: &str) -> Result<PathBuf, FalconError> {
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !valid {
+        return Err(FalconError::Config(format!(
+            "invalid case name `{}` (use letters, digits, '-', '_' or '.')",
+            name
+        )));
+    }
+    Ok(dir.join(format!("{}.{}", name, EXTENSION)))
+}
+
+/// The header as written on its line; sealed records authenticate it.
+fn header_aad(header: &Header) -> Result<Vec<u8>, FalconError> {
+    serde_json::to_vec(header).map_err(|_| FalconError::Unknown)
+}
+
+fn parse_header(line: &str, path: &Path) -> Result<Header, FalconError> {
+    match serde_json::from_str::<Header>(line) {
+        Ok(header) if header.format == FORMAT && header.version == VERSION => Ok(header),
+        _ => Err(FalconError::Config(format!(
+            "{}: not a case file",
+            path.display()
+        ))),
+    }
+}
+
+fn case_error(path: &Path, err: std::io::Error) -> FalconError {
+    FalconError::Config(format!("{}: {}", path.display(), err))
+}
@@ -0,0 +1,304 @@
+//! Authenticated encryption for data at rest (disk cache, saved files).
+//!
+//! Keys come from a passphrase or a key file and are stretched with Argon2id
+//! over a random salt; data is sealed with XChaCha20-Poly1305 under a fresh
+//! random nonce. A sealed known value (`check`) is stored with the salt so a
+//! wrong key is rejected up front instead of yielding unreadable records.
+
+use std::{fmt, fs, path::Path, path::PathBuf};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD as B64, Engine as _};
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng, Payload},
+    XChaCha20Poly1305, XNonce,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::FalconError;
+
+/// Environment variable holding the passphrase when no key file is given.
+pub const PASSPHRASE_ENV: &str = "BLOODY_FALCON_PASSPHRASE";
+
+const KDF: &str = "argon2id";
+/// Argon2id cost for new keys; stored with every key so that a change in the
+/// argon2 crate's defaults cannot make existing data undecryptable.
+const M_COST: u32 = 19 * 1024;
+const T_COST: u32 = 2;
+const P_COST: u32 = 1;
+const ARGON2_VERSION: u32 = 0x13;
+const CHECK: &[u8] = b"bloody-falcon key check";
+const NONCE_LEN: usize = 24;
+const SEALED_FORMAT: &str = "bloody-falcon-sealed";
+
+/// Where the secret comes from.
+#[derive(Clone)]
+pub enum KeySource {
+    Passphrase(String),
+    KeyFile(PathBuf),
+}
+
+impl fmt::Debug for KeySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeySource::Passphrase(_) => f.write_str("Passphrase(<redacted>)"),
+            KeySource::KeyFile(path) => f.debug_tuple("KeyFile").field(path).finish(),
+        }
+    }
+}
+
+impl KeySource {
+    /// `key_file` if given, else the passphrase in [`PASSPHRASE_ENV`], else none.
+    pub fn resolve(key_file: Option<&str>) -> Option<Self> {
+        match key_file {
+            Some(path) => Some(KeySource::KeyFile(PathBuf::from(path))),
+            None => Self::from_env(PASSPHRASE_ENV),
+        }
+    }
+
+    /// Passphrase from the environment variable `var`, if set and non-empty.
+    pub fn from_env(var: &str) -> Option<Self> {
+        std::env::var(var)
+            .ok()
+            .filter(|p| !p.is_empty())
+            .map(KeySource::Passphrase)
+    }
+
+    /// Derive a key under a fresh salt.
+    pub fn create(&self) -> Result<Sealer, FalconError> {
+        let mut salt = [0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+        let params = KeyParams {
+            kdf: KDF.to_string(),
+            m_cost: M_COST,
+            t_cost: T_COST,
+            p_cost: P_COST,
+            version: ARGON2_VERSION,
+            salt: B64.encode(salt),
+            check: String::new(),
+        };
+        let mut sealer = Sealer {
+            cipher: self.derive(&params, &salt)?,
+            params,
+        };
+        sealer.params.check = sealer.seal(CHECK);
+        Ok(sealer)
+    }
+
+    /// Re-derive the key described by `params`, failing on a wrong secret.
+    pub fn unlock(&self, params: &KeyParams) -> Result<Sealer, FalconError> {
+        if params.kdf != KDF {
+            return Err(FalconError::Crypto(format!(
+                "unsupported kdf {}",
+                params.kdf
+            )));
+        }
+        let salt = B64
+            .decode(&params.salt)
+            .map_err(|_| FalconError::Crypto("corrupt salt".into()))?;
+        let sealer = Sealer {
+            cipher: self.derive(params, &salt)?,
+            params: params.clone(),
+        };
+        match sealer.open(&params.check) {
+            Ok(check) if check == CHECK => Ok(sealer),
+            _ => Err(FalconError::Crypto("wrong key or passphrase".into())),
+        }
+    }
+
+    fn derive(&self, params: &KeyParams, salt: &[u8]) -> Result<XChaCha20Poly1305, FalconError> {
+        let secret = match self {
+            KeySource::Passphrase(passphrase) => passphrase.as_bytes().to_vec(),
+            KeySource::KeyFile(path) => fs::read(path)
+                .map_err(|e| FalconError::Crypto(format!("{}: {}", path.display(), e)))?,
+        };
+        if secret.is_empty() {
+            return Err(FalconError::Crypto("empty key".into()));
+        }
+        let version = Version::try_from(params.version).map_err(|_| {
+            FalconError::Crypto(format!("unsupported argon2 version {}", params.version))
+        })?;
+        let cost = Params::new(params.m_cost, params.t_cost, params.p_cost, Some(32))
+            .map_err(|e| FalconError::Crypto(format!("bad key parameters: {}", e)))?;
+        let mut key = [0u8; 32];
+        Argon2::new(Algorithm::Argon2id, version, cost)
+            .hash_password_into(&secret, salt, &mut key)
+            .map_err(|e| FalconError::Crypto(e.to_string()))?;
+        Ok(XChaCha20Poly1305::new(&key.into()))
+    }
+}
+
+/// Public parameters needed to re-derive a key; stored next to the data.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct KeyParams {
+    pub kdf: String,
+    /// Argon2 memory cost in KiB.
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+    pub version: u32,
+    pub salt: String,
+    pub check: String,
+}
+
+/// A derived key ready to seal and open data.
+#[derive(Clone)]
+pub struct Sealer {
+    cipher: XChaCha20Poly1305,
+    params: KeyParams,
+}
+
+impl Sealer {
+    pub fn params(&self) -> &KeyParams {
+        &self.params
+    }
+
+    /// Encrypt `plain` to base64 `nonce || ciphertext`.
+    pub fn seal(&self, plain: &[u8]) -> String {
+        self.seal_bound(plain, &[])
+    }
+
+    /// Like [`seal`](Self::seal), also authenticating `aad` (which is not
+    /// stored); opening needs the same `aad`.
+    pub fn seal_bound(&self, plain: &[u8], aad: &[u8]) -> String {
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let sealed = self
+            .cipher
+            .encrypt(&nonce, Payload { msg: plain, aad })
+            .expect("encryption into a Vec cannot fail");
+        let mut out = nonce.to_vec();
+        out.extend(sealed);
+        B64.encode(out)
+    }
+
+    /// Decrypt and authenticate output of [`Sealer::seal`].
+    pub fn open(&self, sealed: &str) -> Result<Vec<u8>, FalconError> {
+        self.open_bound(sealed, &[])
+    }
+
+    /// Decrypt and authenticate output of [`Sealer::seal_bound`].
+    pub fn open_bound(&self, sealed: &str, aad: &[u8]) -> Result<Vec<u8>, FalconError> {
+        let raw = B64
+            .decode(sealed.trim())
+            .map_err(|_| FalconError::Crypto("not sealed data".into()))?;
+        if raw.len() < NONCE_LEN {
+            return Err(FalconError::Crypto("sealed data truncated".into()));
+        }
+        let (nonce, msg) = raw.split_at(NONCE_LEN);
+        self.cipher
+            .decrypt(XNonce::from_slice(nonce), Payload { msg, aad })
+            .map_err(|_| FalconError::Crypto("authentication failed".into()))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SealedHeader {
+    format: String,
+    key: KeyParams,
+}
+
+/// Write `data` to `path`, sealed when a key is given. The header line is
+/// bound to the sealed body, so it cannot be swapped or stripped.
+pub fn write_file(path: &Path, data: &[u8], key: Option<&KeySource>) -> Result<(), FalconError> {
+    let Some(key) = key else {
+        return Ok(fs::write(path, data)?);
+    };
+    let sealer = key.create()?;
+    let header = SealedHeader {
+        format: SEALED_FORMAT.to_string(),
+        key: sealer.params.clone(),
+    };
+    let mut out = serde_json::to_string(&header).map_err(|_| FalconError::Unknown)?;
+    let body = sealer.seal_bound(data, out.as_bytes());
+    out.push('\n');
+    out.push_str(&body);
+    out.push('\n');
+    Ok(fs::write(path, out)?)
+}
+
+/// Read a file written by [`write_file`]. With a key, a plain file is
+/// rejected rather than trusted; without one it is returned as-is.
+pub fn read_file(path: &Path, key: Option<&KeySource>) -> Result<Vec<u8>, FalconError> {
+    let data = fs::read(path)?;
+    match (sealed_parts(&data), key) {
+        (Some((header, first, rest)), _) => open_sealed(path, &header, first, rest, key),
+        (None, Some(_)) => Err(FalconError::Crypto(format!(
+            "{} is not encrypted but a key is configured; refusing to trust it",
+            path.display()
+        ))),
+        (None, None) => Ok(data),
+    }
+}
+
+/// Read a file the user named as input (an export, saved results): sealed
+/// files are opened, plain ones are accepted, with a warning when a key is
+/// configured.
+pub fn read_input(path: &Path, key: Option<&KeySource>) -> Result<Vec<u8>, FalconError> {
+    let data = fs::read(path)?;
+    match sealed_parts(&data) {
+        Some((header, first, rest)) => open_sealed(path, &header, first, rest, key),
+        None => {
+            if key.is_some() {
+                tracing::warn!(
+                    "{} is not encrypted; reading it as plaintext",
+                    path.display()
+                );
+            }
+            Ok(data)
+        }
+    }
+}
+
+/// Re-seal `path` under `new` (plaintext when `None`), keeping its
+/// modification time. A file already readable with `new` but not with `old`
+/// is left alone, so an interrupted rekey can be run again; returns whether
+/// the file was rewritten.
+pub fn rekey_file(
+    path: &Path,
+    old: Option<&KeySource>,
+    new: Option<&KeySource>,
+) -> Result<bool, FalconError> {
+    let data = match read_input(path, old) {
+        Ok(data) => data,
+        Err(e) => match new.map(|new| read_file(path, Some(new))) {
+            Some(Ok(_)) => return Ok(false),
+            _ => return Err(e),
+        },
+    };
+    let modified = fs::metadata(path)?.modified()?;
+    let tmp = path.with_extension("rekey.tmp");
+    write_file(&tmp, &data, new)?;
+    fs::File::options()
+        .write(true)
+        .open(&tmp)?
+        .set_modified(modified)?;
+    fs::rename(&tmp, path)?;
+    Ok(true)
+}
+
+/// Header, header line and body of a sealed file, or `None` for a plain one.
+fn sealed_parts(data: &[u8]) -> Option<(SealedHeader, &str, &str)> {
+    let text = std::str::from_utf8(data).ok()?;
+    let (first, rest) = text.split_once('\n')?;
+    let header = serde_json::from_str::<SealedHeader>(first).ok()?;
+    (header.format == SEALED_FORMAT).then_some((header, first, rest))
+}
+
+fn open_sealed(
+    path: &Path,
+    header: &SealedHeader,
+    first: &str,
+    rest: &str,
+    key: Option<&KeySource>,
+) -> Result<Vec<u8>, FalconError> {
+    let key = key.ok_or_else(|| {
+        FalconError::Crypto(format!(
+            "{} is encrypted; pass --key-file or set {}",
+            path.display(),
+            PASSPHRASE_ENV
+        ))
+    })?;
+    key.unlock(&header.key)?
+        .open_bound(rest, first.as_bytes())
+        .map_err(|e| FalconError::Crypto(format!("{}: {}", path.display(), e)))
+}
@@ -1,82 +1,662 @@
-use std::{collections::HashMap, fs, path::Path, time::Duration};
+//! Persistent cache as an append-only JSON-lines log with compaction.
+//!
+//! The first line is a header naming the format and a generation; every
+//! following line is one `put` or `delete` record. Writes append a single
+//! line and sync it, so a crash can at worst leave a torn last line, which
+//! replay leaves alone and the next append cuts off. Any other unreadable
+//! line is an error rather than being skipped. Compaction rewrites the live
+//! entries to a temp file and renames it over the log, bumping the
+//! generation so other readers reload.
+//!
+//! Processes sharing a log coordinate through an advisory lock on
+//! `<log>.lock`: writers (appends, compaction, migration) hold it exclusively
+//! across their read-modify-write, readers hold it shared. The log itself
+//! cannot carry the lock because compaction replaces it.
+//!
+//! With a key, the header carries the key parameters and every record line is
+//! sealed on its own (see [`crate::core::crypto`]), so appends stay single
+//! lines and a wrong key is rejected when the log is opened. Each sealed line
+//! also authenticates the header, so records cannot be moved between
+//! generations and the header's `encryption` field cannot be stripped.
+
+use std::{
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use serde::{Deserialize, Serialize};
 
-use crate::core::{engine::ReconResult, error::FalconError};
+use crate::core::{
+    crypto::{self, KeyParams, KeySource, Sealer},
+    engine::ReconResult,
+    error::FalconError,
+};
+
+const FORMAT: &str = "bloody-falcon-cache";
+const VERSION: u32 = 1;
+/// Compact once dead records outnumber live ones by this factor (plus slack).
+const COMPACT_RATIO: usize = 2;
+const COMPACT_SLACK: usize = 64;
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 struct StoredEntry {
     result: ReconResult,
-    timestamp_ms: u128,
+    timestamp_ms: u64,
+    /// Per-entry lifetime; entries without one use the caller's TTL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ttl_ms: Option<u64>,
+    /// Hash of the provider definition and tool version that produced the result.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fingerprint: Option<String>,
+}
+
+impl StoredEntry {
+    fn is_fresh(&self, ttl: Duration) -> bool {
+        let ttl = self.ttl_ms.unwrap_or(ttl.as_millis() as u64);
+        now_ms().saturating_sub(self.timestamp_ms) < ttl
+    }
+}
+
+/// A cache entry as listed, exported and imported.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CacheEntry {
+    pub key: String,
+    pub result: ReconResult,
+    pub timestamp_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+}
+
+impl CacheEntry {
+    pub fn age(&self) -> Duration {
+        Duration::from_millis(now_ms().saturating_sub(self.timestamp_ms))
+    }
+
+    /// Serialized size in bytes.
+    pub fn size(&self) -> usize {
+        serde_json::to_vec(self).map_or(0, |v| v.len())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Header {
+    format: String,
+    version: u32,
+    generation: u128,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    encryption: Option<KeyParams>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "op", rename_all = "lowercase")]
+enum Record {
+    Put {
+        key: String,
+        entry: Box<StoredEntry>,
+    },
+    Delete {
+        key: String,
+    },
+}
+
+#[derive(Default)]
+struct State {
+    index: HashMap<String, StoredEntry>,
+    generation: u128,
+    /// Bytes of the log already applied to `index`.
+    offset: u64,
+    /// Records in the log, live or not; drives compaction.
+    records: usize,
+    /// Key for the current generation when the log is encrypted.
+    sealer: Option<Sealer>,
+    /// The current header, authenticated by every sealed record.
+    aad: Vec<u8>,
 }
 
 pub struct DiskCache {
-    path: std::path::PathBuf,
+    path: PathBuf,
+    key: Option<KeySource>,
+    state: Mutex<State>,
 }
 
 impl DiskCache {
+    /// Open (or create) the log at `path`.
+    ///
+    /// A legacy whole-file JSON cache found at `path`, or at `cache.json`
+    /// next to it, is migrated into the log. An unreadable file is an error
+    /// rather than being treated as empty.
     pub fn new(path: &Path) -> Result<Self, FalconError> {
+        Self::open(path, None)
+    }
+
+    /// Open the log, sealing records with `key`.
+    ///
+    /// An encrypted log fails to open without the right key. A plaintext log
+    /// opened with a key is read as-is and encrypted by the first write.
+    pub fn open(path: &Path, key: Option<KeySource>) -> Result<Self, FalconError> {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent).map_err(|e| FalconError::Config(e.to_string()))?;
         }
-        if !path.exists() {
-            fs::write(path, b"{}\n").map_err(|e| FalconError::Config(e.to_string()))?;
-        }
-        Ok(Self {
+        let cache = Self {
             path: path.to_path_buf(),
-        })
+            key,
+            state: Mutex::new(State::default()),
+        };
+        let file_lock = cache.lock_file(true)?;
+        let fresh = || cache.key.as_ref().map(KeySource::create).transpose();
+
+        let legacy_sibling = path.with_file_name("cache.json");
+        if path.exists() && !is_log(path)? {
+            cache.migrate_legacy(path, fresh()?.as_ref())?;
+        } else if !path.exists() && legacy_sibling != path && legacy_sibling.exists() {
+            cache.migrate_legacy(&legacy_sibling, fresh()?.as_ref())?;
+        } else if !path.exists() {
+            cache.rewrite(&HashMap::new(), fresh()?.as_ref())?;
+        }
+        let mut state = cache.lock();
+        cache.refresh(&mut state)?;
+        if cache.key.is_some() && state.sealer.is_none() {
+            tracing::warn!(
+                "cache at {} is not encrypted; it will be encrypted on the next write",
+                cache.path.display()
+            );
+        }
+        drop(file_lock);
+        drop(state);
+        Ok(cache)
+    }
+
+    /// Rewrite the log under `key`, or in plaintext when `None`.
+    pub fn rekey(&self, key: Option<&KeySource>) -> Result<(), FalconError> {
+        let mut state = self.lock();
+        let _file_lock = self.lock_file(true)?;
+        self.refresh(&mut state)?;
+        let sealer = key.map(KeySource::create).transpose()?;
+        self.rewrite(&state.index, sealer.as_ref())?;
+        state.sealer = sealer;
+        self.reload(&mut state)
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.lock().sealer.is_some()
     }
 
     pub fn get(&self, username: &str, ttl: Duration) -> Result<Option<ReconResult>, FalconError> {
-        let map = self.read_map()?;
-        if let Some(entry) = map.get(username) {
-            let age = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_millis()
-                .saturating_sub(entry.timestamp_ms);
-            if age < ttl.as_millis() {
-                return Ok(Some(entry.result.clone()));
-            }
-        }
-        Ok(None)
+        let mut state = self.lock();
+        let _file_lock = self.lock_file(false)?;
+        self.refresh(&mut state)?;
+        Ok(state
+            .index
+            .get(username)
+            .filter(|entry| entry.is_fresh(ttl))
+            .map(|entry| entry.result.clone()))
+    }
+
+    /// Like [`get`](Self::get), but only for an entry stamped with `fingerprint`;
+    /// entries from another provider definition or tool version are ignored.
+    pub fn get_matching(
+        &self,
+        key: &str,
+        ttl: Duration,
+        fingerprint: &str,
+    ) -> Result<Option<ReconResult>, FalconError> {
+        let mut state = self.lock();
+        let _file_lock = self.lock_file(false)?;
+        self.refresh(&mut state)?;
+        Ok(state
+            .index
+            .get(key)
+            .filter(|entry| entry.is_fresh(ttl))
+            .filter(|entry| entry.fingerprint.as_deref() == Some(fingerprint))
+            .map(|entry| entry.result.clone()))
     }
 
     pub fn put(&self, username: &str, result: &ReconResult) -> Result<(), FalconError> {
-        let mut map = self.read_map()?;
-        let now_ms = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis();
-        map.insert(
-            username.to_string(),
-            StoredEntry {
+        self.store(username, result, None, None)
+    }
+
+    /// Store an entry that expires after `ttl` regardless of the TTL passed to
+    /// `get`, stamped with `fingerprint` when given.
+    pub fn put_with_ttl(
+        &self,
+        key: &str,
+        result: &ReconResult,
+        ttl: Duration,
+        fingerprint: Option<&str>,
+    ) -> Result<(), FalconError> {
+        self.store(
+            key,
+            result,
+            Some(ttl.as_millis() as u64),
+            fingerprint.map(str::to_string),
+        )
+    }
+
+    fn store(
+        &self,
+        key: &str,
+        result: &ReconResult,
+        ttl_ms: Option<u64>,
+        fingerprint: Option<String>,
+    ) -> Result<(), FalconError> {
+        let mut state = self.lock();
+        let _file_lock = self.lock_file(true)?;
+        self.refresh(&mut state)?;
+        let record = Record::Put {
+            key: key.to_string(),
+            entry: Box::new(StoredEntry {
                 result: result.clone(),
-                timestamp_ms: now_ms,
-            },
-        );
-        self.write_map(&map)
+                timestamp_ms: now_ms(),
+                ttl_ms,
+                fingerprint,
+            }),
+        };
+        self.append(&mut state, &[record])?;
+        self.maybe_compact(&mut state)
+    }
+
+    /// Delete entries older than `ttl`; returns how many were removed.
+    pub fn purge_expired(&self, ttl: Duration) -> Result<usize, FalconError> {
+        let mut state = self.lock();
+        let _file_lock = self.lock_file(true)?;
+        self.refresh(&mut state)?;
+        let expired: Vec<Record> = state
+            .index
+            .iter()
+            .filter(|(_, entry)| !entry.is_fresh(ttl))
+            .map(|(key, _)| Record::Delete { key: key.clone() })
+            .collect();
+        if expired.is_empty() {
+            return Ok(0);
+        }
+        self.append(&mut state, &expired)?;
+        self.maybe_compact(&mut state)?;
+        Ok(expired.len())
+    }
+
+    /// Rewrite the log with live entries only.
+    pub fn compact(&self) -> Result<(), FalconError> {
+        let mut state = self.lock();
+        let _file_lock = self.lock_file(true)?;
+        self.refresh(&mut state)?;
+        self.seal_pending(&mut state)?;
+        self.rewrite(&state.index, state.sealer.as_ref())?;
+        self.reload(&mut state)
+    }
+
+    /// Live entries, sorted by key.
+    pub fn entries(&self) -> Result<Vec<CacheEntry>, FalconError> {
+        let mut state = self.lock();
+        let _file_lock = self.lock_file(false)?;
+        self.refresh(&mut state)?;
+        let mut entries: Vec<CacheEntry> = state
+            .index
+            .iter()
+            .map(|(key, entry)| CacheEntry {
+                key: key.clone(),
+                result: entry.result.clone(),
+                timestamp_ms: entry.timestamp_ms,
+                ttl_ms: entry.ttl_ms,
+                fingerprint: entry.fingerprint.clone(),
+            })
+            .collect();
+        entries.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(entries)
+    }
+
+    /// Delete `keys`; returns how many were present.
+    pub fn remove(&self, keys: &[String]) -> Result<usize, FalconError> {
+        let mut state = self.lock();
+        let _file_lock = self.lock_file(true)?;
+        self.refresh(&mut state)?;
+        let deletes: Vec<Record> = keys
+            .iter()
+            .filter(|key| state.index.contains_key(*key))
+            .map(|key| Record::Delete { key: key.clone() })
+            .collect();
+        if deletes.is_empty() {
+            return Ok(0);
+        }
+        self.append(&mut state, &deletes)?;
+        self.maybe_compact(&mut state)?;
+        Ok(deletes.len())
+    }
+
+    /// Merge exported entries, keeping timestamps; an existing newer entry wins.
+    /// Returns how many were taken.
+    pub fn import(&self, entries: Vec<CacheEntry>) -> Result<usize, FalconError> {
+        let mut state = self.lock();
+        let _file_lock = self.lock_file(true)?;
+        self.refresh(&mut state)?;
+        let puts: Vec<Record> = entries
+            .into_iter()
+            .filter(|entry| {
+                state
+                    .index
+                    .get(&entry.key)
+                    .is_none_or(|existing| existing.timestamp_ms < entry.timestamp_ms)
+            })
+            .map(|entry| Record::Put {
+                key: entry.key,
+                entry: Box::new(StoredEntry {
+                    result: entry.result,
+                    timestamp_ms: entry.timestamp_ms,
+                    ttl_ms: entry.ttl_ms,
+                    fingerprint: entry.fingerprint,
+                }),
+            })
+            .collect();
+        if puts.is_empty() {
+            return Ok(0);
+        }
+        self.append(&mut state, &puts)?;
+        self.maybe_compact(&mut state)?;
+        Ok(puts.len())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Size of the log on disk, dead records included.
+    pub fn file_size(&self) -> Result<u64, FalconError> {
+        Ok(fs::metadata(&self.path)
+            .map_err(|e| cache_error(&self.path, e))?
+            .len())
+    }
+
+    /// Number of live entries.
+    pub fn len(&self) -> Result<usize, FalconError> {
+        let mut state = self.lock();
+        let _file_lock = self.lock_file(false)?;
+        self.refresh(&mut state)?;
+        Ok(state.index.len())
+    }
+
+    pub fn is_empty(&self) -> Result<bool, FalconError> {
+        Ok(self.len()? == 0)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().expect("disk cache poisoned")
+    }
+
+    /// Take the cross-process lock on `<log>.lock`, blocking until it is free;
+    /// released when the returned file is dropped.
+    fn lock_file(&self, exclusive: bool) -> Result<File, FalconError> {
+        let mut name = self.path.clone().into_os_string();
+        name.push(".lock");
+        let path = PathBuf::from(name);
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .map_err(|e| cache_error(&path, e))?;
+        let locked = if exclusive {
+            file.lock()
+        } else {
+            file.lock_shared()
+        };
+        locked.map_err(|e| cache_error(&path, e))?;
+        Ok(file)
+    }
+
+    /// Apply records appended since the last read; reload if the log was replaced.
+    fn refresh(&self, state: &mut State) -> Result<(), FalconError> {
+        let mut file = File::open(&self.path).map_err(|e| cache_error(&self.path, e))?;
+        let header = read_header(&mut file, &self.path)?;
+        if header.generation != state.generation {
+            return self.reload(state);
+        }
+        file.seek(SeekFrom::Start(state.offset))?;
+        self.replay(BufReader::new(file), state)
+    }
+
+    fn reload(&self, state: &mut State) -> Result<(), FalconError> {
+        let mut file = File::open(&self.path).map_err(|e| cache_error(&self.path, e))?;
+        let header = read_header(&mut file, &self.path)?;
+        let sealer = match (&header.encryption, state.sealer.take()) {
+            (None, _) => None,
+            (Some(params), Some(sealer)) if sealer.params() == params => Some(sealer),
+            (Some(params), _) => {
+                let key = self.key.as_ref().ok_or_else(|| {
+                    FalconError::Crypto(format!(
+                        "{} is encrypted; pass --key-file or set {}",
+                        self.path.display(),
+                        crate::core::crypto::PASSPHRASE_ENV
+                    ))
+                })?;
+                let sealer = key.unlock(params).map_err(|e| match e {
+                    FalconError::Crypto(msg) => {
+                        FalconError::Crypto(format!("{}: {}", self.path.display(), msg))
+                    }
+                    other => other,
+                })?;
+                Some(sealer)
+            }
+        };
+        *state = State {
+            generation: header.generation,
+            sealer,
+            aad: header_aad(&header)?,
+            ..State::default()
+        };
+        file.seek(SeekFrom::Start(0))?;
+        let mut reader = BufReader::new(file);
+        let mut first = String::new();
+        state.offset = reader.read_line(&mut first)? as u64;
+        self.replay(reader, state)
+    }
+
+    fn replay(&self, mut reader: BufReader<File>, state: &mut State) -> Result<(), FalconError> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = reader.read_line(&mut line)?;
+            // A line without its newline is an append still in flight (or torn); leave it.
+            if read == 0 || !line.ends_with('\n') {
+                return Ok(());
+            }
+            let record = match &state.sealer {
+                Some(sealer) => sealer
+                    .open_bound(&line, &state.aad)
+                    .and_then(|plain| {
+                        serde_json::from_slice::<Record>(&plain)
+                            .map_err(|e| FalconError::Crypto(e.to_string()))
+                    })
+                    .map_err(|e| {
+                        FalconError::Crypto(format!(
+                            "{}: cache record at byte {} is unreadable: {}",
+                            self.path.display(),
+                            state.offset,
+                            e
+                        ))
+                    })?,
+                // Also what a sealed log with a stripped header looks like.
+                None => serde_json::from_str::<Record>(&line).map_err(|e| {
+                    FalconError::Config(format!(
+                        "{}: cache record at byte {} is not a plaintext record: {}",
+                        self.path.display(),
+                        state.offset,
+                        e
+                    ))
+                })?,
+            };
+            state.offset += read as u64;
+            state.records += 1;
+            match record {
+                Record::Put { key, entry } => {
+                    state.index.insert(key, *entry);
+                }
+                Record::Delete { key } => {
+                    state.index.remove(&key);
+                }
+            }
+        }
     }
 
-    pub fn purge_expired(&self, ttl: Duration) -> Result<(), FalconError> {
-        let mut map = self.read_map()?;
-        let now_ms = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis();
-        map.retain(|_, entry| now_ms.saturating_sub(entry.timestamp_ms) < ttl.as_millis());
-        self.write_map(&map)
+    /// Encrypt a plaintext log under the configured key before writing to it.
+    fn seal_pending(&self, state: &mut State) -> Result<(), FalconError> {
+        let Some(key) = self.key.as_ref().filter(|_| state.sealer.is_none()) else {
+            return Ok(());
+        };
+        let sealer = key.create()?;
+        self.rewrite(&state.index, Some(&sealer))?;
+        state.sealer = Some(sealer);
+        self.reload(state)?;
+        tracing::info!("encrypted cache at {}", self.path.display());
+        Ok(())
     }
 
-    fn read_map(&self) -> Result<HashMap<String, StoredEntry>, FalconError> {
-        let data =
-            fs::read_to_string(&self.path).map_err(|e| FalconError::Config(e.to_string()))?;
-        let map: HashMap<String, StoredEntry> = serde_json::from_str(&data).unwrap_or_default();
-        Ok(map)
+    fn append(&self, state: &mut State, records: &[Record]) -> Result<(), FalconError> {
+        self.seal_pending(state)?;
+        let mut buf = Vec::new();
+        for record in records {
+            buf.extend(encode(record, state.sealer.as_ref(), &state.aad)?);
+        }
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| cache_error(&self.path, e))?;
+        // We just replayed everything complete under the write lock, so
+        // anything past the offset is a torn tail left by a crash.
+        if file.metadata()?.len() > state.offset {
+            file.set_len(state.offset)?;
+        }
+        file.write_all(&buf)?;
+        file.sync_data()?;
+        // Pick up our own records (and anything appended just before them).
+        self.refresh(state)
     }
 
-    fn write_map(&self, map: &HashMap<String, StoredEntry>) -> Result<(), FalconError> {
-        let json = serde_json::to_string_pretty(map).map_err(|_| FalconError::Unknown)?;
-        fs::write(&self.path, json).map_err(|e| FalconError::Config(e.to_string()))
+    fn maybe_compact(&self, state: &mut State) -> Result<(), FalconError> {
+        let live = state.index.len();
+        if state.records > live * COMPACT_RATIO + COMPACT_SLACK {
+            self.rewrite(&state.index, state.sealer.as_ref())?;
+            self.reload(state)?;
+        }
+        Ok(())
+    }
+
+    /// Write `entries` as a fresh log generation via temp file + rename.
+    fn rewrite(
+        &self,
+        entries: &HashMap<String, StoredEntry>,
+        sealer: Option<&Sealer>,
+    ) -> Result<(), FalconError> {
+        let header = Header {
+            format: FORMAT.to_string(),
+            version: VERSION,
+            generation: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos(),
+            encryption: sealer.map(|s| s.params().clone()),
+        };
+        let aad = header_aad(&header)?;
+        let tmp = self.path.with_extension("tmp");
+        let mut file = File::create(&tmp).map_err(|e| cache_error(&tmp, e))?;
+        let mut buf = aad.clone();
+        buf.push(b'\n');
+        for (key, entry) in entries {
+            let record = Record::Put {
+                key: key.clone(),
+                entry: Box::new(entry.clone()),
+            };
+            buf.extend(encode(&record, sealer, &aad)?);
+        }
+        file.write_all(&buf)?;
+        file.sync_all()?;
+        fs::rename(&tmp, &self.path).map_err(|e| cache_error(&self.path, e))
     }
+
+    /// Import a whole-file JSON cache (`{"user": {result, timestamp_ms}}`).
+    fn migrate_legacy(&self, legacy: &Path, sealer: Option<&Sealer>) -> Result<(), FalconError> {
+        let data = fs::read_to_string(legacy).map_err(|e| cache_error(legacy, e))?;
+        let entries: HashMap<String, StoredEntry> = if data.trim().is_empty() {
+            HashMap::new()
+        } else {
+            serde_json::from_str(&data).map_err(|e| {
+                FalconError::Config(format!(
+                    "{} is neither a cache log nor a legacy JSON cache: {}",
+                    legacy.display(),
+                    e
+                ))
+            })?
+        };
+        // The backup is sealed like the log so migration never leaves a
+        // plaintext copy behind.
+        let backup = legacy.with_extension("json.migrated");
+        crypto::write_file(&backup, data.as_bytes(), self.key.as_ref())?;
+        self.rewrite(&entries, sealer)?;
+        if legacy != self.path {
+            fs::remove_file(legacy).map_err(|e| cache_error(legacy, e))?;
+        }
+        tracing::info!(
+            "migrated {} cache entries from {} (backup at {})",
+            entries.len(),
+            legacy.display(),
+            backup.display()
+        );
+        Ok(())
+    }
+}
+
+/// One log line for `record`, sealed (bound to the header `aad`) when the
+/// log is encrypted.
+fn encode(record: &Record, sealer: Option<&Sealer>, aad: &[u8]) -> Result<Vec<u8>, FalconError> {
+    let json = serde_json::to_vec(record).map_err(|_| FalconError::Unknown)?;
+    let mut line = match sealer {
+        Some(sealer) => sealer.seal_bound(&json, aad).into_bytes(),
+        None => json,
+    };
+    line.push(b'\n');
+    Ok(line)
+}
+
+/// The header as written on its line; sealed records authenticate it.
+fn header_aad(header: &Header) -> Result<Vec<u8>, FalconError> {
+    serde_json::to_vec(header).map_err(|_| FalconError::Unknown)
+}
+
+fn is_log(path: &Path) -> Result<bool, FalconError> {
+    let mut file = File::open(path).map_err(|e| cache_error(path, e))?;
+    Ok(read_header(&mut file, path).is_ok())
+}
+
+fn read_header(file: &mut File, path: &Path) -> Result<Header, FalconError> {
+    let mut first = Vec::new();
+    let mut byte = [0u8; 1];
+    while file.read(&mut byte)? == 1 && byte[0] != b'\n' {
+        first.push(byte[0]);
+    }
+    let header: Header = serde_json::from_slice(&first)
+        .map_err(|_| FalconError::Config(format!("{}: missing cache header", path.display())))?;
+    if header.format != FORMAT || header.version != VERSION {
+        return Err(FalconError::Config(format!(
+            "{}: unsupported cache format {} v{}",
+            path.display(),
+            header.format,
+            header.version
+        )));
+    }
+    Ok(header)
+}
+
+fn cache_error(path: &Path, err: std::io::Error) -> FalconError {
+    FalconError::Config(format!("{}: {}", path.display(), err))
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
 }
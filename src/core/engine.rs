@@ -4,30 +4,158 @@ use std::{
     time::{Duration, Instant},
 };
 
+use sha2::{Digest, Sha256};
 use tokio::sync::Semaphore;
 
 use crate::{
     config::{AppConfig, ProviderConfig},
+    core::crypto::KeySource,
     core::disk_cache::DiskCache,
     core::error::FalconError,
-    modules::recon::username::check_provider,
+    core::evidence::{Capture, EvidenceRef, EvidenceVault},
+    modules::{
+        crosslink::{
+            extract_identifiers,
+            pivot::{PivotPolicy, PivotQueue, Provenance},
+            similarity::{score_profiles, AccountSimilarity},
+            IdentifierKind, LinkedIdentifier,
+        },
+        recon::{
+            domain::{endpoint_url, is_hostname, lookup_rdap, search_ct, DomainIntel, Lookup},
+            metadata::{extract_metadata, ProfileMetadata},
+            username::{check_provider, profile_url, ProviderCheck},
+        },
+    },
 };
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// What a target identifier names.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum TargetKind {
+    #[default]
+    Username,
+    Domain,
+}
+
+impl TargetKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TargetKind::Username => "username",
+            TargetKind::Domain => "domain",
+        }
+    }
+
+    /// Split `domain:example.com` style input; anything else is a username.
+    pub fn parse_input(input: &str) -> (TargetKind, String) {
+        match input.split_once(':') {
+            Some((prefix, rest)) if prefix.eq_ignore_ascii_case("domain") => {
+                (TargetKind::Domain, rest.trim().to_ascii_lowercase())
+            }
+            _ => (TargetKind::Username, input.to_string()),
+        }
+    }
+
+    /// Cache key for one provider's outcome on `id`.
+    fn cache_key(&self, provider: &str, id: &str) -> String {
+        match self {
+            TargetKind::Username => format!("{}:{}", provider, id),
+            TargetKind::Domain => format!("{}:domain:{}", provider, id),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ReconResult {
+    #[serde(default)]
+    pub target: String,
+    #[serde(default)]
+    pub kind: TargetKind,
     pub hits: usize,
     pub platforms: Vec<String>,
     pub failed: Vec<String>,
     pub restricted: Vec<String>,
     pub rate_limited: Vec<String>,
+    #[serde(default)]
+    pub profiles: Vec<ProfileHit>,
+    /// Set on targets derived by pivoting.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<Provenance>,
+    /// Pairwise "same person" scores between hits with profile metadata.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub similarity: Vec<AccountSimilarity>,
+    /// Registration and CT data for domain targets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain: Option<DomainIntel>,
+    /// Providers whose outcome was served from cache rather than queried.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cached: Vec<String>,
+    /// Captures in the evidence vault backing the hits (`--evidence`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub evidence: Vec<EvidenceRef>,
+}
+
+impl ReconResult {
+    /// Fold a single provider's partial result into this one.
+    fn absorb(&mut self, part: ReconResult) {
+        self.hits += part.hits;
+        self.platforms.extend(part.platforms);
+        self.failed.extend(part.failed);
+        self.restricted.extend(part.restricted);
+        self.rate_limited.extend(part.rate_limited);
+        self.profiles.extend(part.profiles);
+        self.evidence.extend(part.evidence);
+        self.domain = match (self.domain.take(), part.domain) {
+            (Some(mut intel), Some(more)) => {
+                intel.merge(more);
+                Some(intel)
+            }
+            (intel, more) => intel.or(more),
+        };
+    }
+
+    /// Record a domain endpoint outcome; returns the payload and raw
+    /// response when found.
+    fn tally<T>(
+        &mut self,
+        provider: &str,
+        lookup: Result<Lookup<T>, FalconError>,
+    ) -> Option<(T, Capture)> {
+        match lookup {
+            Ok(Lookup::Found(found, capture)) => {
+                self.hits += 1;
+                self.platforms.push(provider.to_string());
+                return Some((found, capture));
+            }
+            Ok(Lookup::NotFound) => {}
+            Ok(Lookup::Restricted) => self.restricted.push(provider.to_string()),
+            Ok(Lookup::RateLimited) => self.rate_limited.push(provider.to_string()),
+            Err(err) => self.failed.push(format!("{}: {}", provider, err)),
+        }
+        None
+    }
+}
+
+/// Profile found on a provider, with identifiers linked from its page.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ProfileHit {
+    pub provider: String,
+    pub url: String,
+    #[serde(default)]
+    pub linked: Vec<LinkedIdentifier>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ProfileMetadata>,
 }
 
 #[derive(Clone, Debug)]
 pub struct CachedResult {
     pub result: ReconResult,
     pub timestamp: Instant,
+    pub ttl: Duration,
+    pub fingerprint: String,
 }
 
 pub struct Engine {
@@ -36,6 +164,7 @@ pub struct Engine {
     semaphore: Arc<Semaphore>,
     cache: Mutex<HashMap<String, CachedResult>>,
     disk_cache: Option<DiskCache>,
+    evidence: Option<EvidenceVault>,
 }
 
 impl Engine {
@@ -49,18 +178,30 @@ impl Engine {
             .map_err(FalconError::from)?;
 
         let disk_cache = if config.disk_cache_enabled {
-            Some(DiskCache::new(std::path::Path::new(
-                &config.disk_cache_path,
-            ))?)
+            Some(DiskCache::open(
+                std::path::Path::new(&config.disk_cache_path),
+                KeySource::resolve(config.key_file.as_deref()),
+            )?)
         } else {
             None
         };
 
+        let evidence = config.evidence_enabled.then(|| {
+            let vault = EvidenceVault::new(&config, KeySource::resolve(config.key_file.as_deref()));
+            match vault.prune() {
+                Ok(0) => {}
+                Ok(n) => tracing::info!("pruned {} evidence capture(s) past retention", n),
+                Err(e) => tracing::warn!("evidence prune failed: {}", e),
+            }
+            vault
+        });
+
         Ok(Self {
             client,
             semaphore: Arc::new(Semaphore::new(config.max_concurrent_requests)),
             cache: Mutex::new(HashMap::new()),
             disk_cache,
+            evidence,
             config,
         })
     }
@@ -70,87 +211,381 @@ impl Engine {
         username: &str,
         use_cache: bool,
     ) -> Result<ReconResult, FalconError> {
-        if use_cache {
-            if let Some(result) = self.check_cache(username) {
-                return Ok(result);
+        let mut result = ReconResult {
+            target: username.to_string(),
+            kind: TargetKind::Username,
+            ..Default::default()
+        };
+
+        for provider in self.config.providers.iter().filter(|p| p.enabled) {
+            let key = TargetKind::Username.cache_key(&provider.name, username);
+            if use_cache {
+                if let Some(part) = self.cached(TargetKind::Username, &provider.name, &key) {
+                    result.absorb(part);
+                    result.cached.push(provider.name.clone());
+                    continue;
+                }
+            }
+            let part = self.check_username(provider, username).await?;
+            if use_cache {
+                self.store(TargetKind::Username, &provider.name, &key, &part);
             }
-            if let Some(disk) = &self.disk_cache {
-                match disk.get(username, Duration::from_secs(self.config.cache_ttl_seconds)) {
-                    Ok(Some(result)) => return Ok(result),
-                    Ok(None) => {}
-                    Err(e) => tracing::warn!("disk cache read error: {}", e),
+            result.absorb(part);
+        }
+
+        result.similarity = score_profiles(&result.profiles);
+        Ok(result)
+    }
+
+    /// Check one provider, returning its outcome as a partial result.
+    async fn check_username(
+        &self,
+        provider: &ProviderConfig,
+        username: &str,
+    ) -> Result<ReconResult, FalconError> {
+        let permit = self
+            .semaphore
+            .acquire()
+            .await
+            .map_err(|_| FalconError::Unknown)?;
+        let ok = check_one(&self.client, provider, username).await;
+        drop(permit);
+
+        let mut part = ReconResult {
+            target: username.to_string(),
+            ..Default::default()
+        };
+        let name = provider.name.clone();
+        match ok {
+            Ok(ProviderCheck {
+                outcome,
+                body,
+                capture,
+            }) => match outcome {
+                ProviderOutcome::Hit => {
+                    part.hits = 1;
+                    if let Some(capture) = capture {
+                        self.keep_evidence(&mut part, &name, &capture);
+                    }
+                    let body = body.unwrap_or_default();
+                    part.profiles.push(ProfileHit {
+                        provider: name.clone(),
+                        url: profile_url(provider, username),
+                        linked: extract_identifiers(&body, &name, username, &self.config.providers),
+                        metadata: extract_metadata(&body, &name),
+                    });
+                    part.platforms.push(name);
+                }
+                ProviderOutcome::Miss => {}
+                ProviderOutcome::Restricted => part.restricted.push(name),
+                ProviderOutcome::RateLimited => part.rate_limited.push(name),
+            },
+            Err(err) => part.failed.push(format!("{}: {}", name, err)),
+        }
+        Ok(part)
+    }
+
+    /// Store `capture` behind `provider`'s hit in `part` when `--evidence` is on.
+    fn keep_evidence(&self, part: &mut ReconResult, provider: &str, capture: &Capture) {
+        let Some(vault) = &self.evidence else {
+            return;
+        };
+        match vault.store(&part.target, provider, capture) {
+            Ok(evidence) => part.evidence.push(evidence),
+            Err(e) => tracing::warn!("evidence capture for {} failed: {}", provider, e),
+        }
+    }
+
+    /// Look up a domain's RDAP record and CT-log hostnames.
+    pub async fn scan_domain(
+        &self,
+        domain: &str,
+        use_cache: bool,
+    ) -> Result<ReconResult, FalconError> {
+        if !is_hostname(domain) {
+            return Err(FalconError::Config(format!(
+                "invalid domain `{}`: expected a hostname like example.com",
+                domain
+            )));
+        }
+        let mut result = ReconResult {
+            target: domain.to_string(),
+            kind: TargetKind::Domain,
+            ..Default::default()
+        };
+
+        for source in ["rdap", "ct"] {
+            let key = TargetKind::Domain.cache_key(source, domain);
+            if use_cache {
+                if let Some(part) = self.cached(TargetKind::Domain, source, &key) {
+                    result.absorb(part);
+                    result.cached.push(source.to_string());
+                    continue;
                 }
             }
+            let part = match source {
+                "rdap" => self.check_rdap(domain).await?,
+                _ => self.check_ct(domain).await?,
+            };
+            if use_cache {
+                self.store(TargetKind::Domain, source, &key, &part);
+            }
+            result.absorb(part);
         }
+        Ok(result)
+    }
 
-        let mut hits = 0usize;
-        let mut platforms = Vec::new();
-        let mut failed = Vec::new();
-        let mut restricted = Vec::new();
-        let mut rate_limited = Vec::new();
+    async fn check_rdap(&self, domain: &str) -> Result<ReconResult, FalconError> {
+        let mut part = ReconResult {
+            target: domain.to_string(),
+            kind: TargetKind::Domain,
+            ..Default::default()
+        };
+        let rdap = {
+            let _permit = self
+                .semaphore
+                .acquire()
+                .await
+                .map_err(|_| FalconError::Unknown)?;
+            lookup_rdap(&self.client, &self.config.rdap_url, domain).await
+        };
+        if let Some((rdap, capture)) = part.tally("rdap", rdap) {
+            self.keep_evidence(&mut part, "rdap", &capture);
+            part.profiles.push(ProfileHit {
+                provider: "rdap".to_string(),
+                url: endpoint_url(&self.config.rdap_url, domain),
+                linked: rdap
+                    .emails
+                    .iter()
+                    .map(|email| LinkedIdentifier {
+                        kind: IdentifierKind::Email,
+                        value: email.clone(),
+                        provider: None,
+                    })
+                    .collect(),
+                metadata: Some(ProfileMetadata {
+                    organization: rdap.registrar.clone(),
+                    created_at: rdap.registered,
+                    last_active: rdap.last_changed,
+                    ..Default::default()
+                }),
+            });
+            part.domain = Some(rdap);
+        }
+        Ok(part)
+    }
 
-        for provider in self.config.providers.iter().filter(|p| p.enabled).cloned() {
-            let permit = self
+    async fn check_ct(&self, domain: &str) -> Result<ReconResult, FalconError> {
+        let mut part = ReconResult {
+            target: domain.to_string(),
+            kind: TargetKind::Domain,
+            ..Default::default()
+        };
+        let ct = {
+            let _permit = self
                 .semaphore
                 .acquire()
                 .await
                 .map_err(|_| FalconError::Unknown)?;
-            let ok = check_one(&self.client, &provider, username).await;
-            drop(permit);
+            search_ct(&self.client, &self.config.ct_url, domain).await
+        };
+        if let Some((hostnames, capture)) = part.tally("ct", ct) {
+            self.keep_evidence(&mut part, "ct", &capture);
+            part.profiles.push(ProfileHit {
+                provider: "ct".to_string(),
+                url: endpoint_url(&self.config.ct_url, domain),
+                linked: hostnames
+                    .iter()
+                    .filter(|h| h.as_str() != domain)
+                    .map(|h| LinkedIdentifier {
+                        kind: IdentifierKind::Domain,
+                        value: h.clone(),
+                        provider: None,
+                    })
+                    .collect(),
+                metadata: None,
+            });
+            part.domain = Some(DomainIntel {
+                hostnames,
+                ..Default::default()
+            });
+        }
+        Ok(part)
+    }
 
-            match ok {
-                Ok(ProviderOutcome::Hit) => {
-                    hits += 1;
-                    platforms.push(provider.name);
-                }
-                Ok(ProviderOutcome::Miss) => {}
-                Ok(ProviderOutcome::Restricted) => restricted.push(provider.name),
-                Ok(ProviderOutcome::RateLimited) => rate_limited.push(provider.name),
-                Err(err) => failed.push(format!("{}: {}", provider.name, err)),
+    /// Assemble a target's result from cache alone; `None` if nothing is cached.
+    pub fn cached_result(&self, kind: TargetKind, id: &str) -> Option<ReconResult> {
+        let sources: Vec<String> = match kind {
+            TargetKind::Username => self
+                .config
+                .providers
+                .iter()
+                .filter(|p| p.enabled)
+                .map(|p| p.name.clone())
+                .collect(),
+            TargetKind::Domain => vec!["rdap".to_string(), "ct".to_string()],
+        };
+        let mut result = ReconResult {
+            target: id.to_string(),
+            kind,
+            ..Default::default()
+        };
+        for source in sources {
+            if let Some(part) = self.cached(kind, &source, &kind.cache_key(&source, id)) {
+                result.absorb(part);
+                result.cached.push(source);
             }
         }
+        if result.cached.is_empty() {
+            return None;
+        }
+        result.similarity = score_profiles(&result.profiles);
+        Some(result)
+    }
 
-        let result = ReconResult {
-            hits,
-            platforms,
-            failed,
-            restricted,
-            rate_limited,
+    /// Scan a target of either kind.
+    pub async fn scan_target(
+        &self,
+        kind: TargetKind,
+        id: &str,
+        use_cache: bool,
+    ) -> Result<ReconResult, FalconError> {
+        match kind {
+            TargetKind::Username => self.scan_username(id, use_cache).await,
+            TargetKind::Domain => self.scan_domain(id, use_cache).await,
+        }
+    }
+
+    /// Hash of what produced `source`'s answers: the tool version and the
+    /// provider's URL template (or the RDAP / CT endpoint for domains). Disk
+    /// entries stamped with another fingerprint are not served.
+    fn fingerprint(&self, kind: TargetKind, source: &str) -> String {
+        let definition = match (kind, source) {
+            (TargetKind::Domain, "rdap") => self.config.rdap_url.as_str(),
+            (TargetKind::Domain, _) => self.config.ct_url.as_str(),
+            (TargetKind::Username, _) => self
+                .config
+                .providers
+                .iter()
+                .find(|p| p.name == source)
+                .map_or("", |p| p.base_url.as_str()),
         };
+        let mut hasher = Sha256::new();
+        for part in [env!("CARGO_PKG_VERSION"), source, definition] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        hasher.finalize()[..8]
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
 
-        if use_cache && self.config.cache_ttl_seconds > 0 {
-            let mut cache = self.cache.lock().expect("cache poisoned");
-            cache.insert(
-                username.to_string(),
-                CachedResult {
-                    result: result.clone(),
-                    timestamp: Instant::now(),
-                },
-            );
-            if let Some(disk) = &self.disk_cache {
-                let _ = disk.purge_expired(Duration::from_secs(self.config.cache_ttl_seconds));
-                if let Err(e) = disk.put(username, &result) {
-                    tracing::warn!("disk cache write error: {}", e);
+    fn cached(&self, kind: TargetKind, source: &str, key: &str) -> Option<ReconResult> {
+        let fingerprint = self.fingerprint(kind, source);
+        let result = self.check_cache(key, &fingerprint).or_else(|| {
+            let disk = self.disk_cache.as_ref()?;
+            let ttl = Duration::from_secs(self.config.cache_ttl_seconds);
+            match disk.get_matching(key, ttl, &fingerprint) {
+                Ok(found) => found,
+                Err(e) => {
+                    tracing::warn!("disk cache read error: {}", e);
+                    None
                 }
             }
-        }
-
-        Ok(result)
+        })?;
+        // Entries written before transient outcomes were excluded.
+        self.ttl_for(&result).map(|_| result)
     }
 
-    fn check_cache(&self, username: &str) -> Option<ReconResult> {
-        if self.config.cache_ttl_seconds == 0 {
+    /// Cache lifetime for a single-provider outcome; `None` means never cache
+    /// it. A zero `cache_ttl_seconds` turns the cache off for every outcome.
+    fn ttl_for(&self, part: &ReconResult) -> Option<Duration> {
+        let config = &self.config;
+        let secs = if config.cache_ttl_seconds == 0
+            || !part.failed.is_empty()
+            || !part.rate_limited.is_empty()
+        {
             return None;
+        } else if part.hits > 0 {
+            config.cache_ttl_seconds
+        } else if !part.restricted.is_empty() {
+            config
+                .restricted_cache_ttl_seconds
+                .unwrap_or(config.cache_ttl_seconds)
+        } else {
+            config
+                .negative_cache_ttl_seconds
+                .unwrap_or(config.cache_ttl_seconds)
+        };
+        (secs > 0).then(|| Duration::from_secs(secs))
+    }
+
+    fn store(&self, kind: TargetKind, source: &str, key: &str, result: &ReconResult) {
+        let Some(ttl) = self.ttl_for(result) else {
+            return;
+        };
+        let fingerprint = self.fingerprint(kind, source);
+        let mut cache = self.cache.lock().expect("cache poisoned");
+        cache.insert(
+            key.to_string(),
+            CachedResult {
+                result: result.clone(),
+                timestamp: Instant::now(),
+                ttl,
+                fingerprint: fingerprint.clone(),
+            },
+        );
+        if let Some(disk) = &self.disk_cache {
+            let _ = disk.purge_expired(Duration::from_secs(self.config.cache_ttl_seconds));
+            if let Err(e) = disk.put_with_ttl(key, result, ttl, Some(&fingerprint)) {
+                tracing::warn!("disk cache write error: {}", e);
+            }
         }
-        let ttl = Duration::from_secs(self.config.cache_ttl_seconds);
-        let cache = self.cache.lock().ok()?;
-        cache.get(username).and_then(|entry| {
-            if entry.timestamp.elapsed() < ttl {
-                Some(entry.result.clone())
-            } else {
-                None
+    }
+
+    /// Scan `root`, then every identifier it reveals, breadth-first within `policy`.
+    pub async fn scan_with_pivots(
+        &self,
+        kind: TargetKind,
+        root: &str,
+        use_cache: bool,
+        policy: PivotPolicy,
+    ) -> Result<Vec<ReconResult>, FalconError> {
+        let mut queue = PivotQueue::new(policy);
+        queue.push_root(kind, root);
+        let mut results = Vec::new();
+        while let Some(next) = queue.pop() {
+            let scan = self.scan_target(next.kind, &next.username, use_cache).await;
+            let mut result = match scan {
+                Ok(result) => result,
+                // A derived target failing should not sink the whole run.
+                Err(e) if next.provenance.is_some() => {
+                    tracing::warn!("pivot scan of {} failed: {}", next.username, e);
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            result.provenance = next.provenance;
+            for derived in queue.offer(&result) {
+                tracing::info!(
+                    "pivot: queued {} (from {})",
+                    derived.username,
+                    result.target
+                );
             }
-        })
+            results.push(result);
+        }
+        Ok(results)
+    }
+
+    fn check_cache(&self, key: &str, fingerprint: &str) -> Option<ReconResult> {
+        let cache = self.cache.lock().ok()?;
+        cache
+            .get(key)
+            .filter(|entry| entry.timestamp.elapsed() < entry.ttl)
+            .filter(|entry| entry.fingerprint == fingerprint)
+            .map(|entry| entry.result.clone())
     }
 }
 
@@ -158,11 +593,14 @@ async fn check_one(
     client: &reqwest::Client,
     provider: &ProviderConfig,
     username: &str,
-) -> Result<ProviderOutcome, FalconError> {
+) -> Result<ProviderCheck, FalconError> {
     let mut delay = Duration::from_millis(300);
     for attempt in 0..3 {
         match check_provider(client, provider, username).await? {
-            ProviderOutcome::RateLimited if attempt < 2 => {
+            ProviderCheck {
+                outcome: ProviderOutcome::RateLimited,
+                ..
+            } if attempt < 2 => {
                 tokio::time::sleep(delay).await;
                 delay *= 2;
                 continue;
@@ -170,7 +608,11 @@ async fn check_one(
             other => return Ok(other),
         }
     }
-    Ok(ProviderOutcome::RateLimited)
+    Ok(ProviderCheck {
+        outcome: ProviderOutcome::RateLimited,
+        body: None,
+        capture: None,
+    })
 }
 #[derive(Debug, Clone)]
 pub enum ProviderOutcome {
@@ -10,6 +10,8 @@ pub enum FalconError {
     Http(String),
     #[error("config error: {0}")]
     Config(String),
+    #[error("crypto error: {0}")]
+    Crypto(String),
     #[error("provider error: {0}")]
     Provider(String),
     #[error("unknown error")]
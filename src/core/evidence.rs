@@ -0,0 +1,295 @@
+//! Opt-in evidence vault: what a provider returned for a hit, kept
+//! content-addressed so a capture can later be shown to be unaltered.
+//!
+//! Hits on username providers and RDAP/CT finds on domains are captured.
+//! Each capture (request URL, status, headers, capped body, timestamp) is
+//! serialized to JSON and stored under `objects/<aa>/<sha256>.json`, named by
+//! the SHA-256 of that JSON. Results carry an [`EvidenceRef`] with the hash;
+//! [`EvidenceVault::load`] re-hashes on read. With a key the objects are
+//! sealed like every other saved file, and the hash is over the plaintext.
+//! Old captures are pruned by age and total size.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    config::AppConfig,
+    core::{
+        crypto::{self, KeySource},
+        error::FalconError,
+    },
+};
+
+/// Response headers whose values are replaced before storing.
+const REDACTED_HEADERS: &[&str] = &["set-cookie", "authorization", "proxy-authorization"];
+
+/// Raw response of a provider hit, as handed over by the recon checks.
+#[derive(Clone, Debug, Default)]
+pub struct Capture {
+    pub url: String,
+    /// URL after redirects.
+    pub final_url: String,
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    /// Whether `body` was already cut short when it was read.
+    pub truncated: bool,
+}
+
+/// A stored capture.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EvidenceRecord {
+    pub captured_at: DateTime<Utc>,
+    pub target: String,
+    pub provider: String,
+    pub request_url: String,
+    pub final_url: String,
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    /// Body snapshot, base64, at most `evidence_max_body_bytes`.
+    pub body_base64: String,
+    /// SHA-256 of the snapshot bytes.
+    pub body_sha256: String,
+    /// Whether the body was longer than the snapshot.
+    pub body_truncated: bool,
+}
+
+impl EvidenceRecord {
+    pub fn body(&self) -> Result<Vec<u8>, FalconError> {
+        BASE64
+            .decode(&self.body_base64)
+            .map_err(|e| FalconError::Config(format!("evidence body: {}", e)))
+    }
+}
+
+/// Pointer from a result to a stored capture.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EvidenceRef {
+    pub provider: String,
+    pub sha256: String,
+    pub captured_at: DateTime<Utc>,
+}
+
+/// Outcome of [`EvidenceVault::verify`].
+#[derive(Debug, Default)]
+pub struct VerifyReport {
+    pub ok: usize,
+    /// Hashes whose objects failed to read or no longer match their name.
+    pub bad: Vec<(String, String)>,
+}
+
+pub struct EvidenceVault {
+    dir: PathBuf,
+    key: Option<KeySource>,
+    max_body_bytes: usize,
+    retention: Option<Duration>,
+    max_total_bytes: u64,
+}
+
+impl EvidenceVault {
+    pub fn new(config: &AppConfig, key: Option<KeySource>) -> Self {
+        Self {
+            dir: PathBuf::from(&config.evidence_dir),
+            key,
+            max_body_bytes: config.evidence_max_body_bytes,
+            retention: (config.evidence_retention_days > 0)
+                .then(|| Duration::from_secs(config.evidence_retention_days * 86_400)),
+            max_total_bytes: config.evidence_max_total_mb * 1024 * 1024,
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Key the vault's objects are sealed with.
+    pub fn key(&self) -> Option<&KeySource> {
+        self.key.as_ref()
+    }
+
+    /// Store `capture` for `provider`'s hit on `target`.
+    pub fn store(
+        &self,
+        target: &str,
+        provider: &str,
+        capture: &Capture,
+    ) -> Result<EvidenceRef, FalconError> {
+        let snapshot = &capture.body[..capture.body.len().min(self.max_body_bytes)];
+        let record = EvidenceRecord {
+            captured_at: Utc::now(),
+            target: target.to_string(),
+            provider: provider.to_string(),
+            request_url: capture.url.clone(),
+            final_url: capture.final_url.clone(),
+            status: capture.status,
+            headers: capture
+                .headers
+                .iter()
+                .map(|(name, value)| {
+                    let value = if REDACTED_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+                        "[redacted]".to_string()
+                    } else {
+                        value.clone()
+                    };
+                    (name.clone(), value)
+                })
+                .collect(),
+            body_base64: BASE64.encode(snapshot),
+            body_sha256: sha256_hex(snapshot),
+            body_truncated: capture.truncated || snapshot.len() < capture.body.len(),
+        };
+        let json = serde_json::to_vec_pretty(&record).map_err(|_| FalconError::Unknown)?;
+        let hash = sha256_hex(&json);
+        let path = self.object_path(&hash);
+        if !path.exists() {
+            fs::create_dir_all(path.parent().expect("object path has a parent"))?;
+            let tmp = path.with_extension("tmp");
+            crypto::write_file(&tmp, &json, self.key.as_ref())?;
+            fs::rename(&tmp, &path)?;
+        }
+        Ok(EvidenceRef {
+            provider: provider.to_string(),
+            sha256: hash,
+            captured_at: record.captured_at,
+        })
+    }
+
+    /// Read a capture, failing if it no longer matches its hash.
+    pub fn load(&self, sha256: &str) -> Result<EvidenceRecord, FalconError> {
+        if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(FalconError::Config(format!(
+                "not an evidence hash: {}",
+                sha256
+            )));
+        }
+        let sha256 = sha256.to_ascii_lowercase();
+        let path = self.object_path(&sha256);
+        if !path.exists() {
+            return Err(FalconError::Config(format!(
+                "no evidence {} in {} (pruned?)",
+                sha256,
+                self.dir.display()
+            )));
+        }
+        let json = crypto::read_file(&path, self.key.as_ref())?;
+        let actual = sha256_hex(&json);
+        if actual != sha256 {
+            return Err(FalconError::Crypto(format!(
+                "evidence {} was altered (content hashes to {})",
+                sha256, actual
+            )));
+        }
+        serde_json::from_slice(&json)
+            .map_err(|e| FalconError::Config(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Hashes of every stored capture.
+    pub fn list(&self) -> Result<Vec<String>, FalconError> {
+        let mut hashes: Vec<String> = self
+            .objects()?
+            .into_iter()
+            .filter_map(|(path, _, _)| Some(path.file_stem()?.to_str()?.to_string()))
+            .collect();
+        hashes.sort();
+        Ok(hashes)
+    }
+
+    /// Re-hash every stored capture.
+    pub fn verify(&self) -> Result<VerifyReport, FalconError> {
+        let mut report = VerifyReport::default();
+        for hash in self.list()? {
+            match self.load(&hash) {
+                Ok(_) => report.ok += 1,
+                Err(e) => report.bad.push((hash, e.to_string())),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Re-seal every capture under `new` (plaintext when `None`), checking
+    /// each against its hash first. Capture times are kept, so retention is
+    /// unaffected. Returns how many were rewritten.
+    pub fn rekey(&self, new: Option<&KeySource>) -> Result<usize, FalconError> {
+        let mut rekeyed = 0;
+        for hash in self.list()? {
+            let path = self.object_path(&hash);
+            if let Err(e) = self.load(&hash) {
+                // Already moved by an interrupted rekey?
+                if crypto::read_file(&path, new).is_ok_and(|json| sha256_hex(&json) == hash) {
+                    continue;
+                }
+                return Err(e);
+            }
+            crypto::rekey_file(&path, self.key.as_ref(), new)?;
+            rekeyed += 1;
+        }
+        Ok(rekeyed)
+    }
+
+    /// Drop captures older than the retention period, then the oldest ones
+    /// until the vault fits its size cap. Returns how many were removed.
+    pub fn prune(&self) -> Result<usize, FalconError> {
+        let now = SystemTime::now();
+        let mut objects = self.objects()?;
+        objects.sort_by_key(|(_, modified, _)| *modified);
+        let mut total: u64 = objects.iter().map(|(_, _, size)| size).sum();
+        let mut removed = 0;
+        for (path, modified, size) in objects {
+            let expired = self.retention.is_some_and(|retention| {
+                now.duration_since(modified).unwrap_or_default() > retention
+            });
+            if !expired && total <= self.max_total_bytes {
+                continue;
+            }
+            fs::remove_file(&path)?;
+            total -= size;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    fn object_path(&self, sha256: &str) -> PathBuf {
+        self.dir
+            .join("objects")
+            .join(&sha256[..2])
+            .join(format!("{}.json", sha256))
+    }
+
+    /// `(path, modified, size)` of every stored object.
+    fn objects(&self) -> Result<Vec<(PathBuf, SystemTime, u64)>, FalconError> {
+        let root = self.dir.join("objects");
+        if !root.exists() {
+            return Ok(Vec::new());
+        }
+        let mut objects = Vec::new();
+        for shard in fs::read_dir(&root)? {
+            let shard = shard?.path();
+            if !shard.is_dir() {
+                continue;
+            }
+            for entry in fs::read_dir(&shard)? {
+                let path = entry?.path();
+                if path.extension().is_some_and(|ext| ext == "json") {
+                    let meta = fs::metadata(&path)?;
+                    objects.push((path, meta.modified()?, meta.len()));
+                }
+            }
+        }
+        Ok(objects)
+    }
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
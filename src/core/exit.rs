@@ -0,0 +1,71 @@
+//! Process exit codes for headless runs, so scripts can branch on outcomes.
+
+use std::process::ExitCode;
+
+use clap::ValueEnum;
+
+use crate::core::engine::ReconResult;
+
+/// How a headless run ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExitStatus {
+    /// At least one account or domain record was found.
+    Hits = 0,
+    /// Every check completed and nothing was found.
+    NoHits = 1,
+    /// The run could not complete (bad config, unreadable file, ...), or a
+    /// check matched a `--fail-on` policy. Also what clap uses for usage
+    /// errors.
+    Fatal = 2,
+    /// Results were produced, but a batch target failed outright or a check
+    /// failed or stayed rate limited.
+    Partial = 3,
+}
+
+impl From<ExitStatus> for ExitCode {
+    fn from(status: ExitStatus) -> Self {
+        ExitCode::from(status as u8)
+    }
+}
+
+/// Degraded provider outcomes that `--fail-on` turns into a fatal run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum FailOn {
+    /// A provider kept answering 429.
+    RateLimited,
+    /// A check errored (network, timeout, 5xx).
+    Failed,
+    /// A provider answered 401/403.
+    Restricted,
+}
+
+impl FailOn {
+    fn matches(self, result: &ReconResult) -> bool {
+        match self {
+            FailOn::RateLimited => !result.rate_limited.is_empty(),
+            FailOn::Failed => !result.failed.is_empty(),
+            FailOn::Restricted => !result.restricted.is_empty(),
+        }
+    }
+}
+
+/// Exit status for `results`, given how many batch targets could not be
+/// scanned at all. A `--fail-on` match is fatal; otherwise partial failures
+/// take precedence over hits.
+pub fn classify(results: &[ReconResult], skipped: usize, fail_on: &[FailOn]) -> ExitStatus {
+    let escalated = results
+        .iter()
+        .any(|result| fail_on.iter().any(|policy| policy.matches(result)));
+    let degraded = results
+        .iter()
+        .any(|result| !result.failed.is_empty() || !result.rate_limited.is_empty());
+    if escalated {
+        ExitStatus::Fatal
+    } else if skipped > 0 || degraded {
+        ExitStatus::Partial
+    } else if results.iter().any(|result| result.hits > 0) {
+        ExitStatus::Hits
+    } else {
+        ExitStatus::NoHits
+    }
+}
@@ -1 +1,88 @@
-//! Logger placeholder for centralized event tracking.
+//! Log routing for the TUI: tracing output is collected here and shown in
+//! the in-app log panel instead of being written over the alternate screen.
+
+use std::{
+    collections::VecDeque,
+    io,
+    sync::{Arc, Mutex},
+};
+
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Lines kept while the UI is not draining them.
+const MAX_PENDING: usize = 100;
+
+const LEVELS: &[&str] = &["TRACE", "DEBUG", "INFO", "WARN", "ERROR"];
+
+/// Shared queue of formatted log lines; a `MakeWriter` for a fmt layer.
+#[derive(Clone, Debug, Default)]
+pub struct LogFeed {
+    lines: Arc<Mutex<VecDeque<String>>>,
+}
+
+impl LogFeed {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take every line logged since the last call.
+    pub fn drain(&self) -> Vec<String> {
+        match self.lines.lock() {
+            Ok(mut lines) => lines.drain(..).collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn push(&self, line: String) {
+        if let Ok(mut lines) = self.lines.lock() {
+            if lines.len() >= MAX_PENDING {
+                lines.pop_front();
+            }
+            lines.push_back(line);
+        }
+    }
+}
+
+impl<'a> MakeWriter<'a> for LogFeed {
+    type Writer = FeedWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        FeedWriter {
+            feed: self.clone(),
+            buf: Vec::new(),
+        }
+    }
+}
+
+/// Buffers one formatted event and queues its lines when dropped.
+pub struct FeedWriter {
+    feed: LogFeed,
+    buf: Vec<u8>,
+}
+
+impl io::Write for FeedWriter {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(data);
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for FeedWriter {
+    fn drop(&mut self) {
+        let text = String::from_utf8_lossy(&self.buf);
+        for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            // "WARN message" -> "[WARN] message", matching the panel's own entries.
+            let line = match line.split_once(' ') {
+                Some((level, rest)) if LEVELS.contains(&level) => {
+                    format!("[{}] {}", level, rest.trim_start())
+                }
+                _ => line.to_string(),
+            };
+            self.feed.push(line);
+        }
+    }
+}
@@ -1,6 +1,10 @@
 //! Core engine wiring stubs.
 
+pub mod case;
+pub mod crypto;
 pub mod disk_cache;
 pub mod engine;
 pub mod error;
+pub mod evidence;
+pub mod exit;
 pub mod logger;
@@ -0,0 +1,173 @@
+//! Versioned wrapper for headless JSON output.
+//!
+//! The shape is published as a JSON Schema in
+//! `docs/schema/result-envelope.v1.json`; bump [`SCHEMA_VERSION`] (and publish
+//! a new schema file) on any incompatible change.
+
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::AppConfig,
+    core::{
+        crypto::{self, KeySource},
+        engine::{ReconResult, TargetKind},
+        error::FalconError,
+    },
+};
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// One headless run: what was asked, how, and what came back.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Envelope {
+    pub schema_version: u32,
+    pub tool: Tool,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    /// Targets as given; pivoted targets only appear in `results`, and
+    /// targets whose scan failed carry an `error` instead of a result.
+    pub targets: Vec<RequestedTarget>,
+    /// Providers enabled for the run.
+    pub providers: Vec<String>,
+    pub cache: CacheUsage,
+    /// `sha256:` digest of the effective configuration.
+    pub config_hash: String,
+    pub results: Vec<ReconResult>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Tool {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RequestedTarget {
+    pub target: String,
+    pub kind: TargetKind,
+    pub label: Option<String>,
+    /// Why the target has no result, when its scan failed and the batch
+    /// went on without it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CacheUsage {
+    /// Whether cached outcomes could be used (`--no-cache` turns this off).
+    pub enabled: bool,
+    /// Whether the disk cache was consulted.
+    pub disk: bool,
+    /// Provider outcomes served from cache across all results.
+    pub served: usize,
+}
+
+impl Envelope {
+    /// Wrap `results` of a run over `targets` that started at `started_at`.
+    pub fn new(
+        config: &AppConfig,
+        targets: Vec<RequestedTarget>,
+        use_cache: bool,
+        started_at: DateTime<Utc>,
+        results: Vec<ReconResult>,
+    ) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            tool: Tool {
+                name: env!("CARGO_PKG_NAME").to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+            },
+            started_at,
+            finished_at: Utc::now(),
+            targets,
+            providers: config
+                .providers
+                .iter()
+                .filter(|p| p.enabled)
+                .map(|p| p.name.clone())
+                .collect(),
+            cache: CacheUsage {
+                enabled: use_cache,
+                disk: use_cache && config.disk_cache_enabled,
+                served: results.iter().map(|r| r.cached.len()).sum(),
+            },
+            config_hash: config.digest(),
+            results,
+        }
+    }
+}
+
+/// Results read back from a saved output file.
+#[derive(Clone, Debug)]
+pub struct SavedResults {
+    pub results: Vec<ReconResult>,
+    /// When the run finished (from the envelope), else when the file was written.
+    pub scanned_at: DateTime<Utc>,
+}
+
+/// Read saved headless output (possibly sealed) from `path`.
+pub fn read_saved(path: &Path, key: Option<&KeySource>) -> Result<SavedResults, FalconError> {
+    let data = crypto::read_input(path, key)?;
+    let (results, finished_at) = parse_saved(&data)
+        .map_err(|e| FalconError::Config(format!("{}: {}", path.display(), e)))?;
+    let scanned_at = match finished_at {
+        Some(at) => at,
+        None => std::fs::metadata(path)?.modified()?.into(),
+    };
+    Ok(SavedResults {
+        results,
+        scanned_at,
+    })
+}
+
+/// Results from a saved headless output: an envelope, a bare result, an
+/// array of results, or JSON lines.
+pub fn parse_results(data: &[u8]) -> Result<Vec<ReconResult>, String> {
+    parse_saved(data).map(|(results, _)| results)
+}
+
+/// Results plus the envelope's finish time, when there is one.
+fn parse_saved(data: &[u8]) -> Result<(Vec<ReconResult>, Option<DateTime<Utc>>), String> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Saved {
+        Envelope(Box<Envelope>),
+        Many(Vec<ReconResult>),
+        One(Box<ReconResult>),
+    }
+    match serde_json::from_slice::<Saved>(data) {
+        Ok(Saved::Envelope(envelope)) => {
+            if envelope.schema_version > SCHEMA_VERSION {
+                return Err(format!(
+                    "envelope schema version {} is newer than supported ({})",
+                    envelope.schema_version, SCHEMA_VERSION
+                ));
+            }
+            Ok((envelope.results, Some(envelope.finished_at)))
+        }
+        Ok(Saved::Many(results)) => Ok((results, None)),
+        Ok(Saved::One(result)) => Ok((vec![*result], None)),
+        Err(_) => match serde_json::from_slice::<ReconResult>(data) {
+            Ok(result) => Ok((vec![result], None)),
+            Err(e) => {
+                let lines = String::from_utf8_lossy(data);
+                let lines = lines.lines().filter(|line| !line.trim().is_empty());
+                // A single line that failed above is not worth a second error.
+                if lines.clone().nth(1).is_none() {
+                    return Err(e.to_string());
+                }
+                lines
+                    .enumerate()
+                    .map(|(i, line)| {
+                        serde_json::from_str::<ReconResult>(line)
+                            .map_err(|e| format!("line {}: {}", i + 1, e))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(|results| (results, None))
+            }
+        },
+    }
+}
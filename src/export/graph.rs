@@ -0,0 +1,293 @@
+//! Graph exporters (GraphML, GEXF, Graphviz DOT) for scan results.
+
+use std::fmt::Write;
+
+use crate::{
+    core::engine::ReconResult,
+    modules::crosslink::{IdentifierKind, LinkedIdentifier},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NodeKind {
+    Target,
+    Provider,
+    Profile,
+    Username,
+    Email,
+    Domain,
+}
+
+impl NodeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NodeKind::Target => "target",
+            NodeKind::Provider => "provider",
+            NodeKind::Profile => "profile",
+            NodeKind::Username => "username",
+            NodeKind::Email => "email",
+            NodeKind::Domain => "domain",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgeKind {
+    /// Target -> profile it was found at.
+    HasProfile,
+    /// Profile or username -> provider hosting it.
+    HostedOn,
+    /// Profile -> identifier linked from its page.
+    LinksTo,
+    /// Parent target -> target derived from it by pivoting.
+    Pivot,
+}
+
+impl EdgeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EdgeKind::HasProfile => "has_profile",
+            EdgeKind::HostedOn => "hosted_on",
+            EdgeKind::LinksTo => "links_to",
+            EdgeKind::Pivot => "pivot",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Node {
+    pub id: String,
+    pub kind: NodeKind,
+    pub label: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct Edge {
+    pub source: String,
+    pub target: String,
+    pub kind: EdgeKind,
+}
+
+/// Deduplicated node/edge view over one or more results.
+#[derive(Clone, Debug, Default)]
+pub struct Graph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+impl Graph {
+    pub fn from_results(results: &[ReconResult]) -> Self {
+        let mut graph = Graph::default();
+        for result in results {
+            let target = graph.node(
+                format!("target:{}", result.target),
+                NodeKind::Target,
+                &result.target,
+            );
+            if let Some(provenance) = &result.provenance {
+                let parent = graph.node(
+                    format!("target:{}", provenance.parent),
+                    NodeKind::Target,
+                    &provenance.parent,
+                );
+                graph.edge(&parent, &target, EdgeKind::Pivot);
+            }
+            for profile in &result.profiles {
+                let provider = graph.node(
+                    format!("provider:{}", profile.provider),
+                    NodeKind::Provider,
+                    &profile.provider,
+                );
+                let url = graph.node(
+                    format!("profile:{}", profile.url),
+                    NodeKind::Profile,
+                    &profile.url,
+                );
+                graph.edge(&target, &url, EdgeKind::HasProfile);
+                graph.edge(&url, &provider, EdgeKind::HostedOn);
+                for linked in &profile.linked {
+                    let ident = graph.identifier(linked);
+                    graph.edge(&url, &ident, EdgeKind::LinksTo);
+                }
+            }
+        }
+        graph
+    }
+
+    fn identifier(&mut self, linked: &LinkedIdentifier) -> String {
+        match linked.kind {
+            IdentifierKind::Email => self.node(
+                format!("email:{}", linked.value),
+                NodeKind::Email,
+                &linked.value,
+            ),
+            IdentifierKind::Domain => self.node(
+                format!("domain:{}", linked.value),
+                NodeKind::Domain,
+                &linked.value,
+            ),
+            IdentifierKind::Username => {
+                let provider = linked.provider.as_deref().unwrap_or("unknown");
+                let id = self.node(
+                    format!("username:{}:{}", provider, linked.value),
+                    NodeKind::Username,
+                    &format!("{} ({})", linked.value, provider),
+                );
+                if let Some(name) = &linked.provider {
+                    let host = self.node(format!("provider:{}", name), NodeKind::Provider, name);
+                    self.edge(&id, &host, EdgeKind::HostedOn);
+                }
+                id
+            }
+        }
+    }
+
+    fn node(&mut self, id: String, kind: NodeKind, label: &str) -> String {
+        if !self.nodes.iter().any(|n| n.id == id) {
+            self.nodes.push(Node {
+                id: id.clone(),
+                kind,
+                label: label.to_string(),
+            });
+        }
+        id
+    }
+
+    fn edge(&mut self, source: &str, target: &str, kind: EdgeKind) {
+        let exists = self
+            .edges
+            .iter()
+            .any(|e| e.source == source && e.target == target && e.kind == kind);
+        if !exists {
+            self.edges.push(Edge {
+                source: source.to_string(),
+                target: target.to_string(),
+                kind,
+            });
+        }
+    }
+}
+
+/// Render results as GraphML (yEd, Gephi).
+pub fn to_graphml(results: &[ReconResult]) -> String {
+    let graph = Graph::from_results(results);
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+    out.push_str("  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n");
+    out.push_str("  <key id=\"kind\" for=\"node\" attr.name=\"kind\" attr.type=\"string\"/>\n");
+    out.push_str(
+        "  <key id=\"relation\" for=\"edge\" attr.name=\"relation\" attr.type=\"string\"/>\n",
+    );
+    out.push_str("  <graph id=\"bloody-falcon\" edgedefault=\"directed\">\n");
+    for node in &graph.nodes {
+        let _ = writeln!(
+            out,
+            "    <node id=\"{}\"><data key=\"label\">{}</data><data key=\"kind\">{}</data></node>",
+            xml_escape(&node.id),
+            xml_escape(&node.label),
+            node.kind.as_str()
+        );
+    }
+    for (i, edge) in graph.edges.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "    <edge id=\"e{}\" source=\"{}\" target=\"{}\"><data key=\"relation\">{}</data></edge>",
+            i,
+            xml_escape(&edge.source),
+            xml_escape(&edge.target),
+            edge.kind.as_str()
+        );
+    }
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+/// Render results as GEXF 1.3 (Gephi).
+pub fn to_gexf(results: &[ReconResult]) -> String {
+    let graph = Graph::from_results(results);
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">\n");
+    out.push_str("  <meta><creator>bloody-f4lcon</creator></meta>\n");
+    out.push_str("  <graph defaultedgetype=\"directed\" mode=\"static\">\n");
+    out.push_str("    <attributes class=\"node\">\n");
+    out.push_str("      <attribute id=\"kind\" title=\"kind\" type=\"string\"/>\n");
+    out.push_str("    </attributes>\n");
+    out.push_str("    <nodes>\n");
+    for node in &graph.nodes {
+        let _ = writeln!(
+            out,
+            "      <node id=\"{}\" label=\"{}\"><attvalues><attvalue for=\"kind\" value=\"{}\"/></attvalues></node>",
+            xml_escape(&node.id),
+            xml_escape(&node.label),
+            node.kind.as_str()
+        );
+    }
+    out.push_str("    </nodes>\n");
+    out.push_str("    <edges>\n");
+    for (i, edge) in graph.edges.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "      <edge id=\"e{}\" source=\"{}\" target=\"{}\" label=\"{}\"/>",
+            i,
+            xml_escape(&edge.source),
+            xml_escape(&edge.target),
+            edge.kind.as_str()
+        );
+    }
+    out.push_str("    </edges>\n");
+    out.push_str("  </graph>\n</gexf>\n");
+    out
+}
+
+/// Render results as a Graphviz digraph.
+pub fn to_dot(results: &[ReconResult]) -> String {
+    let graph = Graph::from_results(results);
+    let mut out = String::from("digraph bloody_falcon {\n  rankdir=LR;\n");
+    for node in &graph.nodes {
+        let shape = match node.kind {
+            NodeKind::Target => "doubleoctagon",
+            NodeKind::Provider => "box",
+            NodeKind::Profile => "note",
+            NodeKind::Username | NodeKind::Email | NodeKind::Domain => "ellipse",
+        };
+        let _ = writeln!(
+            out,
+            "  \"{}\" [label=\"{}\", kind=\"{}\", shape={}];",
+            dot_escape(&node.id),
+            dot_escape(&node.label),
+            node.kind.as_str(),
+            shape
+        );
+    }
+    for edge in &graph.edges {
+        let _ = writeln!(
+            out,
+            "  \"{}\" -> \"{}\" [label=\"{}\"];",
+            dot_escape(&edge.source),
+            dot_escape(&edge.target),
+            edge.kind.as_str()
+        );
+    }
+    out.push_str("}\n");
+    out
+}
+
+pub(crate) fn xml_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn dot_escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
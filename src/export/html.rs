@@ -0,0 +1,303 @@
+//! Self-contained HTML report (inline CSS, no external assets).
+//!
+//! The page layout lives in a template with `{{placeholder}}` slots; the
+//! built-in one is `assets/templates/report.html`, and a copy at the
+//! configured `report_template` path replaces it at run time.
+
+use std::{collections::BTreeSet, fmt::Write, fs, path::Path};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use crate::{
+    core::{engine::ReconResult, error::FalconError},
+    export::graph::xml_escape,
+    modules::diff::provider_states,
+};
+
+/// Template compiled into the binary.
+pub const TEMPLATE: &str = include_str!("../../assets/templates/report.html");
+
+/// Scan metadata shown at the top of the report.
+#[derive(Clone, Debug)]
+pub struct ReportMeta {
+    pub title: String,
+    pub generated_at: DateTime<Utc>,
+    /// Providers enabled for the run; empty lists those seen in the results.
+    pub providers: Vec<String>,
+}
+
+impl Default for ReportMeta {
+    fn default() -> Self {
+        Self {
+            title: "Recon report".to_string(),
+            generated_at: Utc::now(),
+            providers: Vec::new(),
+        }
+    }
+}
+
+/// Read the template at `path`, falling back to the built-in one when it is missing.
+pub fn load_template(path: &Path) -> Result<String, FalconError> {
+    if !path.exists() {
+        return Ok(TEMPLATE.to_string());
+    }
+    fs::read_to_string(path).map_err(|e| FalconError::Config(format!("{}: {}", path.display(), e)))
+}
+
+/// Render `results` into `template`.
+pub fn to_html(results: &[ReconResult], template: &str, meta: &ReportMeta) -> String {
+    let generated_at = meta.generated_at.to_rfc3339_opts(SecondsFormat::Secs, true);
+    fill(template, |slot| match slot {
+        "title" => Some(xml_escape(&meta.title)),
+        "generated_at" => Some(generated_at.clone()),
+        "version" => Some(env!("CARGO_PKG_VERSION").to_string()),
+        "metadata" => Some(metadata(results, meta)),
+        "summary" => Some(summary(results)),
+        "targets" => Some(
+            results
+                .iter()
+                .enumerate()
+                .map(|(i, result)| target_section(i, result))
+                .collect(),
+        ),
+        _ => None,
+    })
+}
+
+/// Substitute `{{slot}}`s in one pass, so rendered text is never re-expanded.
+/// Unknown slots are left as they are.
+fn fill(template: &str, value: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find("}}") {
+            Some(end) => {
+                let slot = &after[..end];
+                match value(slot.trim()) {
+                    Some(text) => out.push_str(&text),
+                    None => {
+                        out.push_str("{{");
+                        out.push_str(slot);
+                        out.push_str("}}");
+                    }
+                }
+                rest = &after[end + 2..];
+            }
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn metadata(results: &[ReconResult], meta: &ReportMeta) -> String {
+    let providers: Vec<String> = if meta.providers.is_empty() {
+        results
+            .iter()
+            .flat_map(|r| provider_states(r).into_keys().chain(r.cached.clone()))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    } else {
+        meta.providers.clone()
+    };
+    let cached: usize = results.iter().map(|r| r.cached.len()).sum();
+    let mut out = String::from("<section id=\"metadata\">\n<h2>Scan</h2>\n<dl>\n");
+    let mut item = |term: &str, value: String| {
+        let _ = writeln!(out, "<dt>{}</dt><dd>{}</dd>", term, value);
+    };
+    item(
+        "Generated",
+        meta.generated_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+    );
+    item("Tool version", env!("CARGO_PKG_VERSION").to_string());
+    item("Targets", results.len().to_string());
+    item(
+        "Providers",
+        if providers.is_empty() {
+            "<span class=\"muted\">none</span>".to_string()
+        } else {
+            xml_escape(&providers.join(", "))
+        },
+    );
+    item("Served from cache", format!("{} provider outcomes", cached));
+    out.push_str("</dl>\n</section>\n");
+    out
+}
+
+fn summary(results: &[ReconResult]) -> String {
+    let total = |count: fn(&ReconResult) -> usize| results.iter().map(count).sum::<usize>();
+    let mut out = String::from("<section id=\"summary\">\n<h2>Summary</h2>\n");
+    let _ = writeln!(
+        out,
+        "<p><span class=\"hit\">{} hits</span> · <span class=\"restricted\">{} restricted</span> · \
+         <span class=\"rate_limited\">{} rate-limited</span> · <span class=\"failed\">{} failed</span> \
+         across {} target{}</p>",
+        total(|r| r.hits),
+        total(|r| r.restricted.len()),
+        total(|r| r.rate_limited.len()),
+        total(|r| r.failed.len()),
+        results.len(),
+        if results.len() == 1 { "" } else { "s" }
+    );
+    out.push_str(
+        "<table>\n<tr><th>Target</th><th>Kind</th><th class=\"num\">Hits</th>\
+         <th class=\"num\">Restricted</th><th class=\"num\">Rate-limited</th>\
+         <th class=\"num\">Failed</th></tr>\n",
+    );
+    for (i, result) in results.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "<tr><td><a href=\"#target-{}\">{}</a></td><td>{}</td><td class=\"num hit\">{}</td>\
+             <td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+            i + 1,
+            xml_escape(&result.target),
+            result.kind.as_str(),
+            result.hits,
+            result.restricted.len(),
+            result.rate_limited.len(),
+            result.failed.len()
+        );
+    }
+    out.push_str("</table>\n</section>\n");
+    out
+}
+
+fn target_section(index: usize, result: &ReconResult) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<section id=\"target-{}\">\n<h2>{} <span class=\"muted\">({})</span></h2>",
+        index + 1,
+        xml_escape(&result.target),
+        result.kind.as_str()
+    );
+    if let Some(provenance) = &result.provenance {
+        let _ = writeln!(
+            out,
+            "<p class=\"muted\">Found via {} on {} (pivot depth {})</p>",
+            xml_escape(&provenance.provider),
+            xml_escape(&provenance.parent),
+            provenance.depth
+        );
+    }
+
+    out.push_str("<h3>Accounts</h3>\n");
+    if result.profiles.is_empty() {
+        out.push_str("<p class=\"muted\">No accounts found.</p>\n");
+    } else {
+        out.push_str(
+            "<table>\n<tr><th>Provider</th><th>Profile</th><th>Name</th>\
+             <th>Created</th><th>Linked identifiers</th></tr>\n",
+        );
+        for profile in &result.profiles {
+            let metadata = profile.metadata.clone().unwrap_or_default();
+            let cached = if result.cached.contains(&profile.provider) {
+                " <span class=\"muted\">(cached)</span>"
+            } else {
+                ""
+            };
+            let linked: Vec<String> = profile
+                .linked
+                .iter()
+                .map(|l| xml_escape(&l.value))
+                .collect();
+            let _ = writeln!(
+                out,
+                "<tr><td class=\"hit\">{}{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                xml_escape(&profile.provider),
+                cached,
+                link(&profile.url),
+                metadata
+                    .display_name
+                    .or(metadata.organization)
+                    .as_deref()
+                    .map_or(String::new(), xml_escape),
+                metadata
+                    .created_at
+                    .map_or(String::new(), |at| at.format("%Y-%m-%d").to_string()),
+                linked.join("<br>")
+            );
+        }
+        out.push_str("</table>\n");
+    }
+
+    if let Some(intel) = &result.domain {
+        out.push_str("<h3>Domain</h3>\n<dl>\n");
+        let date = |at: Option<DateTime<Utc>>| at.map(|at| at.format("%Y-%m-%d").to_string());
+        for (term, value) in [
+            ("Registrar", intel.registrar.clone()),
+            ("Registered", date(intel.registered)),
+            ("Last changed", date(intel.last_changed)),
+            ("Expires", date(intel.expires)),
+            (
+                "Nameservers",
+                (!intel.nameservers.is_empty()).then(|| intel.nameservers.join(", ")),
+            ),
+            (
+                "CT hostnames",
+                (!intel.hostnames.is_empty()).then(|| intel.hostnames.len().to_string()),
+            ),
+        ] {
+            if let Some(value) = value {
+                let _ = writeln!(out, "<dt>{}</dt><dd>{}</dd>", term, xml_escape(&value));
+            }
+        }
+        out.push_str("</dl>\n");
+    }
+
+    let failed: Vec<String> = result
+        .failed
+        .iter()
+        .map(|f| match f.split_once(':') {
+            Some((provider, reason)) => format!(
+                "{} — <span class=\"muted\">{}</span>",
+                xml_escape(provider),
+                xml_escape(reason.trim())
+            ),
+            None => xml_escape(f),
+        })
+        .collect();
+    let escaped = |list: &[String]| list.iter().map(|p| xml_escape(p)).collect::<Vec<_>>();
+    for (heading, class, items) in [
+        ("Restricted", "restricted", escaped(&result.restricted)),
+        (
+            "Rate-limited",
+            "rate_limited",
+            escaped(&result.rate_limited),
+        ),
+        ("Failed", "failed", failed),
+    ] {
+        if items.is_empty() {
+            continue;
+        }
+        let _ = writeln!(
+            out,
+            "<h3 class=\"{}\">{}</h3>\n<ul class=\"{}\">",
+            class, heading, class
+        );
+        for item in items {
+            let _ = writeln!(out, "<li>{}</li>", item);
+        }
+        out.push_str("</ul>\n");
+    }
+    out.push_str("</section>\n");
+    out
+}
+
+/// Link web URLs only; anything else is shown as text.
+fn link(url: &str) -> String {
+    if url.starts_with("https://") || url.starts_with("http://") {
+        format!(
+            "<a href=\"{0}\" rel=\"noopener noreferrer\">{0}</a>",
+            xml_escape(url)
+        )
+    } else {
+        xml_escape(url)
+    }
+}
@@ -0,0 +1,80 @@
+//! Result exporters for headless output and library use.
+
+pub mod envelope;
+pub mod graph;
+pub mod html;
+pub mod notes;
+pub mod siem;
+pub mod stix;
+pub mod tabular;
+pub mod timeline;
+
+use clap::ValueEnum;
+
+use crate::core::{engine::ReconResult, error::FalconError};
+
+/// Output formats selectable with `--format`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    #[default]
+    Json,
+    /// One compact result per line.
+    Jsonl,
+    /// One row per provider per target.
+    Csv,
+    /// Aligned columns for terminals.
+    Table,
+    Markdown,
+    /// One Markdown note per target, written into the `--output` directory.
+    Notes,
+    /// Single-file report with inline CSS.
+    Html,
+    /// STIX 2.1 bundle.
+    Stix,
+    Graphml,
+    Gexf,
+    Dot,
+    TimelineCsv,
+    TimelineJson,
+}
+
+/// Render one or more results in the given format.
+pub fn render(format: Format, results: &[ReconResult]) -> Result<String, FalconError> {
+    match format {
+        Format::Json => {
+            let json = if let [single] = results {
+                serde_json::to_string_pretty(single)
+            } else {
+                serde_json::to_string_pretty(results)
+            };
+            json.map_err(|_| FalconError::Unknown)
+        }
+        Format::Jsonl => tabular::to_jsonl(results),
+        Format::Csv => Ok(tabular::to_csv(results)),
+        Format::Table => Ok(tabular::to_table(results)),
+        Format::Markdown => Ok(tabular::to_markdown(results)),
+        Format::Notes => Err(FalconError::Config(
+            "notes are written one file per target; use notes::write_notes".into(),
+        )),
+        Format::Html => Ok(html::to_html(
+            results,
+            html::TEMPLATE,
+            &html::ReportMeta::default(),
+        )),
+        Format::Stix => stix::to_json(results),
+        Format::Graphml => Ok(graph::to_graphml(results)),
+        Format::Gexf => Ok(graph::to_gexf(results)),
+        Format::Dot => Ok(graph::to_dot(results)),
+        Format::TimelineCsv => Ok(timeline::to_csv(results)),
+        Format::TimelineJson => timeline::to_json(results),
+    }
+}
+
+/// Quote a CSV field when it contains separators, quotes or newlines.
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,248 @@
+//! Markdown notes, one file per target, for Obsidian-style knowledge bases.
+//!
+//! Each note has YAML front matter, a provider table and `[[wikilinks]]` to
+//! the identifiers found on the target's profiles. Everything below
+//! [`NOTES_MARKER`] belongs to the analyst and is carried over when a note is
+//! re-exported; only the generated part above it is replaced. Notes are
+//! always plaintext, since they are meant to be opened in a note app.
+
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    core::{engine::ReconResult, error::FalconError},
+    export::tabular::{md_cell, rows},
+    modules::crosslink::IdentifierKind,
+};
+
+/// Line separating the generated note from the analyst's own notes.
+pub const NOTES_MARKER: &str =
+    "<!-- bloody-f4lcon: notes below this line are kept on re-export -->";
+
+/// Characters Obsidian does not allow in note names.
+const UNSAFE: &[char] = &[
+    '/', '\\', ':', '*', '?', '"', '<', '>', '|', '#', '^', '[', ']',
+];
+
+/// File name (without `.md`) and wikilink target for an identifier.
+pub fn note_name(identifier: &str) -> String {
+    identifier
+        .trim()
+        .chars()
+        .map(|c| if UNSAFE.contains(&c) { '_' } else { c })
+        .collect()
+}
+
+/// `[[name]]`, or `[[name|value]]` when the note name had to be changed.
+fn wikilink(value: &str) -> String {
+    let name = note_name(value);
+    if name == value {
+        format!("[[{}]]", name)
+    } else {
+        format!("[[{}|{}]]", name, value.replace(['[', ']', '|'], ""))
+    }
+}
+
+/// The note for `result`; the notes section of `existing` is kept.
+pub fn render_note(
+    result: &ReconResult,
+    label: Option<&str>,
+    scanned_at: DateTime<Utc>,
+    existing: Option<&str>,
+) -> String {
+    let mut out = String::from("---\n");
+    let _ = writeln!(out, "target: {}", json!(result.target));
+    let _ = writeln!(out, "kind: {}", result.kind.as_str());
+    let _ = writeln!(out, "label: {}", json!(label));
+    let _ = writeln!(
+        out,
+        "scanned_at: {}",
+        scanned_at.to_rfc3339_opts(SecondsFormat::Secs, true)
+    );
+    let _ = writeln!(out, "hits: {}", result.hits);
+    let _ = writeln!(out, "platforms: {}", json!(result.platforms));
+    out.push_str("tags: [bloody-f4lcon]\n---\n\n");
+
+    let _ = writeln!(out, "# {}\n", result.target);
+    if let Some(provenance) = &result.provenance {
+        let _ = writeln!(
+            out,
+            "Found on the {} profile of {} (pivot depth {}).\n",
+            provenance.provider,
+            wikilink(&provenance.parent),
+            provenance.depth
+        );
+    }
+
+    out.push_str("## Providers\n\n");
+    let rows = rows(result);
+    if rows.iter().all(|row| row.provider.is_empty()) {
+        out.push_str("_No accounts found._\n");
+    } else {
+        out.push_str("| Provider | Status | Profile |\n| --- | --- | --- |\n");
+        for row in rows {
+            let status = match row.detail {
+                "" => row.status.to_string(),
+                detail => format!("{} ({})", row.status, md_cell(detail)),
+            };
+            let profile = if row.url.is_empty() {
+                String::new()
+            } else {
+                format!("[{}]({})", md_cell(&row.provider), row.url)
+            };
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} |",
+                md_cell(&row.provider),
+                status,
+                profile
+            );
+        }
+    }
+
+    let mut linked: Vec<String> = Vec::new();
+    for profile in &result.profiles {
+        for identifier in &profile.linked {
+            let what = match (identifier.kind, &identifier.provider) {
+                (IdentifierKind::Username, Some(provider)) => format!("{} username", provider),
+                (IdentifierKind::Username, None) => "username".to_string(),
+                (IdentifierKind::Email, _) => "email".to_string(),
+                (IdentifierKind::Domain, _) => "domain".to_string(),
+            };
+            let line = format!(
+                "- {} ({}, from {})",
+                wikilink(&identifier.value),
+                what,
+                profile.provider
+            );
+            if !linked.contains(&line) {
+                linked.push(line);
+            }
+        }
+    }
+    if let Some(domain) = &result.domain {
+        for email in &domain.emails {
+            linked.push(format!("- {} (email, from RDAP)", wikilink(email)));
+        }
+    }
+    if !linked.is_empty() {
+        out.push_str("\n## Linked identifiers\n\n");
+        for line in linked {
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+
+    if let Some(domain) = &result.domain {
+        out.push_str("\n## Domain\n\n");
+        let date = |at: Option<DateTime<Utc>>| at.map(|at| at.format("%Y-%m-%d").to_string());
+        for (name, value) in [
+            ("Registrar", domain.registrar.clone()),
+            ("Registered", date(domain.registered)),
+            ("Expires", date(domain.expires)),
+        ] {
+            if let Some(value) = value {
+                let _ = writeln!(out, "- {}: {}", name, value);
+            }
+        }
+        if !domain.nameservers.is_empty() {
+            let _ = writeln!(out, "- Nameservers: {}", domain.nameservers.join(", "));
+        }
+        if !domain.hostnames.is_empty() {
+            let _ = writeln!(out, "- Hostnames ({}):", domain.hostnames.len());
+            for host in &domain.hostnames {
+                let _ = writeln!(out, "  - {}", host);
+            }
+        }
+    }
+
+    out.push_str("\n## Notes\n\n");
+    out.push_str(NOTES_MARKER);
+    out.push('\n');
+    match existing {
+        Some(existing) => match existing.split_once(NOTES_MARKER) {
+            Some((_, notes)) => out.push_str(notes.strip_prefix('\n').unwrap_or(notes)),
+            // Not a note we wrote: keep all of it as the analyst's notes.
+            None => out.push_str(existing),
+        },
+        None => out.push('\n'),
+    }
+    out
+}
+
+/// Target named in a note's front matter, if it is one of ours.
+fn note_target(note: &str) -> Option<String> {
+    let front = note.strip_prefix("---\n")?.split("\n---\n").next()?;
+    let value = front
+        .lines()
+        .find_map(|line| line.strip_prefix("target: "))?;
+    serde_json::from_str(value).ok()
+}
+
+/// `<dir>/<name>.md` for `target`. Targets whose names collide (`a/b` and
+/// `a_b`), within the run or with a note already on disk, get a short hash
+/// of the target appended, so no note is overwritten with another subject.
+fn note_path(
+    dir: &Path,
+    target: &str,
+    taken: &HashMap<PathBuf, String>,
+) -> Result<PathBuf, FalconError> {
+    let name = note_name(target);
+    let path = dir.join(format!("{}.md", name));
+    let owner = match taken.get(&path) {
+        Some(owner) => Some(owner.clone()),
+        None if path.exists() => note_target(&String::from_utf8_lossy(&fs::read(&path)?)),
+        None => None,
+    };
+    if owner.as_deref().is_none_or(|owner| owner == target) {
+        return Ok(path);
+    }
+    let hash: String = Sha256::digest(target.as_bytes())
+        .iter()
+        .take(4)
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    Ok(dir.join(format!("{}-{}.md", name, hash)))
+}
+
+/// Write or update `<dir>/<target>.md` for each result, returning the paths.
+/// `labels` maps target ids to their labels.
+pub fn write_notes(
+    results: &[ReconResult],
+    labels: &HashMap<String, String>,
+    dir: &Path,
+    scanned_at: DateTime<Utc>,
+) -> Result<Vec<PathBuf>, FalconError> {
+    fs::create_dir_all(dir)?;
+    let mut written = Vec::new();
+    let mut taken = HashMap::new();
+    for result in results {
+        let path = note_path(dir, &result.target, &taken)?;
+        taken.insert(path.clone(), result.target.clone());
+        let existing = if path.exists() {
+            let data = fs::read(&path)?;
+            Some(String::from_utf8_lossy(&data).into_owned())
+        } else {
+            None
+        };
+        let note = render_note(
+            result,
+            labels.get(&result.target).map(String::as_str),
+            scanned_at,
+            existing.as_deref(),
+        );
+        fs::write(&path, note)?;
+        if !written.contains(&path) {
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
@@ -0,0 +1,320 @@
+//! SIEM sinks: Elasticsearch `_bulk` and Splunk HTTP Event Collector.
+//!
+//! Each result becomes one event carrying the full `ReconResult` plus the
+//! scan time and tool version. Results are posted in batches of
+//! `siem_batch_size`; a batch is retried with backoff on network errors, 429
+//! and 5xx, and given up on (with a warning) after `siem_retries` retries.
+//! `_bulk` reports per document, so only the documents Elasticsearch pushed
+//! back (429/5xx) are retried, and ones it rejected count as failed.
+//! Delivery never fails a run.
+
+use std::{borrow::Cow, time::Duration};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde_json::{json, Value};
+
+use crate::{
+    config::{AppConfig, ElasticsearchConfig, SplunkConfig},
+    core::engine::ReconResult,
+};
+
+/// Delay before the first retry; doubled for each further one, up to
+/// [`MAX_RETRY_DELAY`].
+const RETRY_DELAY: Duration = Duration::from_millis(250);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Results delivered and dropped by one sink.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DeliveryReport {
+    pub sent: usize,
+    pub failed: usize,
+}
+
+/// Outcome of [`SiemSinks::send`]; `None` for sinks that are not configured.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SiemReport {
+    pub elasticsearch: Option<DeliveryReport>,
+    pub splunk: Option<DeliveryReport>,
+}
+
+/// The sinks configured in `[elasticsearch]` / `[splunk]`.
+pub struct SiemSinks {
+    client: Client,
+    elasticsearch: Option<ElasticsearchConfig>,
+    splunk: Option<SplunkConfig>,
+    batch_size: usize,
+    retries: u32,
+}
+
+impl SiemSinks {
+    /// Sinks from `config`, or `None` when no SIEM is configured.
+    pub fn from_config(config: &AppConfig) -> Option<Self> {
+        if config.elasticsearch.is_none() && config.splunk.is_none() {
+            return None;
+        }
+        let client = match Client::builder()
+            .user_agent(config.user_agent.clone())
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()
+        {
+            Ok(client) => client,
+            Err(e) => {
+                tracing::warn!("SIEM delivery disabled: cannot build HTTP client: {}", e);
+                return None;
+            }
+        };
+        Some(Self {
+            client,
+            elasticsearch: config.elasticsearch.clone(),
+            splunk: config.splunk.clone(),
+            batch_size: config.siem_batch_size.max(1),
+            retries: config.siem_retries,
+        })
+    }
+
+    /// Post `results`, scanned at `at`, to every configured sink.
+    pub async fn send(&self, results: &[ReconResult], at: DateTime<Utc>) -> SiemReport {
+        let mut outcome = SiemReport::default();
+        if let Some(es) = &self.elasticsearch {
+            let mut report = DeliveryReport::default();
+            for batch in results.chunks(self.batch_size) {
+                let delivered = self.post_bulk(es, batch, at).await;
+                report.sent += delivered.sent;
+                report.failed += delivered.failed;
+            }
+            tracing::info!(
+                "elasticsearch: {} results sent, {} failed",
+                report.sent,
+                report.failed
+            );
+            outcome.elasticsearch = Some(report);
+        }
+
+        if let Some(splunk) = &self.splunk {
+            let mut report = DeliveryReport::default();
+            let url = format!(
+                "{}/services/collector/event",
+                splunk.url.trim_end_matches('/')
+            );
+            for batch in results.chunks(self.batch_size) {
+                let body = hec_body(batch, splunk, at);
+                let delivered = self
+                    .post("splunk", batch.len(), || {
+                        self.client
+                            .post(&url)
+                            .header("Authorization", format!("Splunk {}", splunk.token))
+                            .header("Content-Type", "application/json")
+                            .body(body.clone())
+                    })
+                    .await;
+                tally(&mut report, delivered, batch.len());
+            }
+            tracing::info!(
+                "splunk: {} results sent, {} failed",
+                report.sent,
+                report.failed
+            );
+            outcome.splunk = Some(report);
+        }
+        outcome
+    }
+
+    /// Send one `_bulk` batch. `_bulk` answers 200 even when single
+    /// documents fail, so each item is checked: pushed-back ones (429/5xx)
+    /// are sent again, rejected ones count as failed.
+    async fn post_bulk(
+        &self,
+        es: &ElasticsearchConfig,
+        batch: &[ReconResult],
+        at: DateTime<Utc>,
+    ) -> DeliveryReport {
+        let url = format!("{}/_bulk", es.url.trim_end_matches('/'));
+        let mut report = DeliveryReport::default();
+        let mut pending = Cow::Borrowed(batch);
+        for attempt in 0..=self.retries {
+            if attempt > 0 {
+                tokio::time::sleep(retry_delay(attempt)).await;
+            }
+            let mut request = self
+                .client
+                .post(&url)
+                .header("Content-Type", "application/x-ndjson")
+                .body(bulk_body(&pending, &es.index, at));
+            if let Some(key) = &es.api_key {
+                request = request.header("Authorization", format!("ApiKey {}", key));
+            }
+            let response = match request.send().await {
+                Ok(response) => response,
+                Err(e) => {
+                    tracing::warn!(
+                        "elasticsearch delivery failed (attempt {}): {}",
+                        attempt + 1,
+                        e
+                    );
+                    continue;
+                }
+            };
+            let status = response.status();
+            if !status.is_success() {
+                if !is_transient(status.as_u16()) {
+                    tracing::warn!("elasticsearch refused {} events: {}", pending.len(), status);
+                    report.failed += pending.len();
+                    return report;
+                }
+                tracing::warn!(
+                    "elasticsearch answered {} (attempt {})",
+                    status,
+                    attempt + 1
+                );
+                continue;
+            }
+            let body = response.json::<Value>().await.unwrap_or(Value::Null);
+            let items = body["items"]
+                .as_array()
+                .filter(|_| body["errors"] == json!(true));
+            let Some(items) = items else {
+                report.sent += pending.len();
+                return report;
+            };
+            let mut retry = Vec::new();
+            let mut rejected = 0;
+            for (result, item) in pending.iter().zip(items) {
+                let item = &item["index"];
+                if !item["error"].is_object() {
+                    report.sent += 1;
+                } else if item["status"]
+                    .as_u64()
+                    .is_some_and(|s| is_transient(s as u16))
+                {
+                    retry.push(result.clone());
+                } else {
+                    rejected += 1;
+                }
+            }
+            // Items missing from the reply cannot be confirmed.
+            rejected += pending.len().saturating_sub(items.len());
+            if rejected > 0 {
+                tracing::warn!(
+                    "elasticsearch rejected {} of {} events",
+                    rejected,
+                    pending.len()
+                );
+            }
+            report.failed += rejected;
+            if retry.is_empty() {
+                return report;
+            }
+            tracing::warn!(
+                "elasticsearch pushed back {} events (attempt {})",
+                retry.len(),
+                attempt + 1
+            );
+            pending = Cow::Owned(retry);
+        }
+        tracing::warn!(
+            "elasticsearch delivery of {} events given up after {} retries",
+            pending.len(),
+            self.retries
+        );
+        report.failed += pending.len();
+        report
+    }
+
+    /// Send one batch, retrying transient failures; true once accepted.
+    async fn post(&self, sink: &str, events: usize, request: impl Fn() -> RequestBuilder) -> bool {
+        for attempt in 0..=self.retries {
+            if attempt > 0 {
+                tokio::time::sleep(retry_delay(attempt)).await;
+            }
+            let response = match request().send().await {
+                Ok(response) => response,
+                Err(e) => {
+                    tracing::warn!("{} delivery failed (attempt {}): {}", sink, attempt + 1, e);
+                    continue;
+                }
+            };
+            let status = response.status();
+            if status.is_success() {
+                return true;
+            }
+            if !is_transient(status.as_u16()) {
+                tracing::warn!("{} refused {} events: {}", sink, events, status);
+                return false;
+            }
+            tracing::warn!("{} answered {} (attempt {})", sink, status, attempt + 1);
+        }
+        tracing::warn!(
+            "{} delivery of {} events given up after {} retries",
+            sink,
+            events,
+            self.retries
+        );
+        false
+    }
+}
+
+/// Backoff before retry number `attempt` (from 1), capped.
+fn retry_delay(attempt: u32) -> Duration {
+    RETRY_DELAY
+        .saturating_mul(2u32.saturating_pow(attempt - 1))
+        .min(MAX_RETRY_DELAY)
+}
+
+/// Statuses worth retrying: 429 and 5xx.
+fn is_transient(status: u16) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS.as_u16() || (500..600).contains(&status)
+}
+
+fn tally(report: &mut DeliveryReport, delivered: bool, events: usize) {
+    if delivered {
+        report.sent += events;
+    } else {
+        report.failed += events;
+    }
+}
+
+/// The event document for `result`.
+fn document(result: &ReconResult) -> Value {
+    let mut doc = serde_json::to_value(result).unwrap_or_else(|_| json!({}));
+    doc["tool"] = json!({
+        "name": env!("CARGO_PKG_NAME"),
+        "version": env!("CARGO_PKG_VERSION"),
+    });
+    doc
+}
+
+/// `_bulk` NDJSON: an `index` action line and a document line per result.
+pub fn bulk_body(results: &[ReconResult], index: &str, at: DateTime<Utc>) -> String {
+    let timestamp = at.to_rfc3339_opts(SecondsFormat::Millis, true);
+    let mut out = String::new();
+    for result in results {
+        let mut doc = document(result);
+        doc["@timestamp"] = json!(timestamp);
+        out.push_str(&json!({ "index": { "_index": index } }).to_string());
+        out.push('\n');
+        out.push_str(&doc.to_string());
+        out.push('\n');
+    }
+    out
+}
+
+/// HEC batch: one event object per result, newline separated.
+pub fn hec_body(results: &[ReconResult], splunk: &SplunkConfig, at: DateTime<Utc>) -> String {
+    let time = at.timestamp_millis() as f64 / 1000.0;
+    let mut out = String::new();
+    for result in results {
+        let mut event = json!({
+            "time": time,
+            "source": env!("CARGO_PKG_NAME"),
+            "sourcetype": splunk.sourcetype,
+            "event": document(result),
+        });
+        if let Some(index) = &splunk.index {
+            event["index"] = json!(index);
+        }
+        out.push_str(&event.to_string());
+        out.push('\n');
+    }
+    out
+}
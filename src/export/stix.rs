@@ -0,0 +1,263 @@
+//! STIX 2.1 bundle export.
+//!
+//! Username targets become `identity` objects and each hit a `user-account`
+//! observable (`account_login` = target, `account_type` = provider), joined
+//! by `related-to` relationships. Domain targets become `domain-name`
+//! observables. Linked identifiers found on a profile are observables related
+//! to that profile's account, and pivoted targets are related to the target
+//! whose profile revealed them. Everything is attributed to a `system`
+//! identity for the tool.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    core::{
+        engine::{ProfileHit, ReconResult, TargetKind},
+        error::FalconError,
+    },
+    modules::crosslink::{IdentifierKind, LinkedIdentifier},
+};
+
+/// Namespace for deterministic cyber-observable ids (STIX 2.1 §2.9).
+const SCO_NAMESPACE: Uuid = Uuid::from_u128(0x00abedb4_aa42_466c_9c01_fed23315a9b7);
+
+/// Render `results` as a STIX 2.1 bundle created at `now`.
+pub fn to_bundle(results: &[ReconResult], now: DateTime<Utc>) -> Value {
+    let mut bundle = Bundle {
+        now: timestamp(now),
+        objects: Vec::new(),
+        producer: random_id("identity"),
+    };
+    bundle.objects.push(json!({
+        "type": "identity",
+        "spec_version": "2.1",
+        "id": bundle.producer,
+        "created": bundle.now,
+        "modified": bundle.now,
+        "name": "bloody-f4lcon",
+        "identity_class": "system",
+        "description": format!("bloody-f4lcon v{} OSINT recon", env!("CARGO_PKG_VERSION")),
+    }));
+
+    let mut roots: HashMap<&str, String> = HashMap::new();
+    for result in results {
+        if !roots.contains_key(result.target.as_str()) {
+            let root = bundle.target(result);
+            roots.insert(&result.target, root);
+        }
+    }
+    for result in results {
+        let root = &roots[result.target.as_str()];
+        for profile in &result.profiles {
+            let from = match result.kind {
+                TargetKind::Username => {
+                    let account =
+                        bundle.account(&result.target, Some(&profile.provider), Some(profile));
+                    bundle.relate(root, &account, format!("{} account", profile.provider));
+                    account
+                }
+                // RDAP / CT records describe the domain itself.
+                TargetKind::Domain => root.clone(),
+            };
+            for linked in &profile.linked {
+                let observable = bundle.linked(linked);
+                bundle.relate(
+                    &from,
+                    &observable,
+                    format!("linked from {} profile", profile.provider),
+                );
+            }
+        }
+        if let Some(provenance) = &result.provenance {
+            if let Some(parent) = roots.get(provenance.parent.as_str()) {
+                let relationship = bundle.relate(
+                    root,
+                    parent,
+                    format!(
+                        "found on the {} profile of {}",
+                        provenance.provider, provenance.parent
+                    ),
+                );
+                relationship["x_bloody_falcon_pivot_depth"] = json!(provenance.depth);
+                relationship["x_bloody_falcon_pivot_identifier"] =
+                    json!(provenance.identifier.value);
+            }
+        }
+    }
+
+    json!({
+        "type": "bundle",
+        "id": random_id("bundle"),
+        "objects": bundle.objects,
+    })
+}
+
+/// Pretty-printed bundle for `--format stix`.
+pub fn to_json(results: &[ReconResult]) -> Result<String, FalconError> {
+    serde_json::to_string_pretty(&to_bundle(results, Utc::now())).map_err(|_| FalconError::Unknown)
+}
+
+struct Bundle {
+    now: String,
+    producer: String,
+    objects: Vec<Value>,
+}
+
+impl Bundle {
+    /// The object standing for a target: an identity, or a domain-name observable.
+    fn target(&mut self, result: &ReconResult) -> String {
+        match result.kind {
+            TargetKind::Username => {
+                let id = random_id("identity");
+                let mut notes = Vec::new();
+                for (label, providers) in [
+                    ("restricted", result.restricted.clone()),
+                    ("rate limited", result.rate_limited.clone()),
+                    ("failed", result.failed.clone()),
+                ] {
+                    if !providers.is_empty() {
+                        notes.push(format!("{}: {}", label, providers.join(", ")));
+                    }
+                }
+                let mut identity = json!({
+                    "type": "identity",
+                    "spec_version": "2.1",
+                    "id": id,
+                    "created": self.now,
+                    "modified": self.now,
+                    "created_by_ref": self.producer,
+                    "name": result.target,
+                    "identity_class": "individual",
+                    "description": format!("{} account(s) found", result.hits),
+                });
+                if !notes.is_empty() {
+                    identity["description"] = json!(format!(
+                        "{} account(s) found; {}",
+                        result.hits,
+                        notes.join("; ")
+                    ));
+                }
+                self.objects.push(identity);
+                id
+            }
+            TargetKind::Domain => self.observable("domain-name", &result.target),
+        }
+    }
+
+    /// A `user-account` observable; a fetched profile adds its URL and metadata.
+    fn account(
+        &mut self,
+        login: &str,
+        provider: Option<&str>,
+        profile: Option<&ProfileHit>,
+    ) -> String {
+        let contributing = match provider {
+            Some(provider) => format!(
+                "{{\"account_login\":{},\"account_type\":{}}}",
+                json!(login),
+                json!(provider)
+            ),
+            None => format!("{{\"account_login\":{}}}", json!(login)),
+        };
+        let id = observable_id("user-account", &contributing);
+        let mut account = json!({
+            "type": "user-account",
+            "spec_version": "2.1",
+            "id": id,
+            "account_login": login,
+        });
+        if let Some(provider) = provider {
+            account["account_type"] = json!(provider);
+        }
+        if let Some(profile) = profile {
+            account["x_bloody_falcon_profile_url"] = json!(profile.url);
+            let metadata = profile.metadata.clone().unwrap_or_default();
+            if let Some(name) = metadata.display_name {
+                account["display_name"] = json!(name);
+            }
+            if let Some(at) = metadata.created_at {
+                account["account_created"] = json!(timestamp(at));
+            }
+            if let Some(at) = metadata.last_active {
+                account["account_last_login"] = json!(timestamp(at));
+            }
+        }
+        self.merge(account);
+        id
+    }
+
+    fn linked(&mut self, linked: &LinkedIdentifier) -> String {
+        match linked.kind {
+            IdentifierKind::Email => self.observable("email-addr", &linked.value),
+            IdentifierKind::Domain => self.observable("domain-name", &linked.value),
+            IdentifierKind::Username => {
+                self.account(&linked.value, linked.provider.as_deref(), None)
+            }
+        }
+    }
+
+    /// A `domain-name` or `email-addr` observable keyed by its value.
+    fn observable(&mut self, kind: &str, value: &str) -> String {
+        let id = observable_id(kind, &format!("{{\"value\":{}}}", json!(value)));
+        self.merge(json!({
+            "type": kind,
+            "spec_version": "2.1",
+            "id": id,
+            "value": value,
+        }));
+        id
+    }
+
+    /// Add a `related-to` relationship.
+    fn relate(&mut self, source: &str, target: &str, description: String) -> &mut Value {
+        let relationship = json!({
+            "type": "relationship",
+            "spec_version": "2.1",
+            "id": random_id("relationship"),
+            "created": self.now,
+            "modified": self.now,
+            "created_by_ref": self.producer,
+            "relationship_type": "related-to",
+            "source_ref": source,
+            "target_ref": target,
+            "description": description,
+        });
+        self.objects.push(relationship);
+        self.objects.last_mut().expect("just pushed")
+    }
+
+    /// Observables have deterministic ids, so the same account or address can
+    /// come up twice; fold the properties of later copies into the first.
+    fn merge(&mut self, object: Value) {
+        let existing = self.objects.iter_mut().find(|o| o["id"] == object["id"]);
+        match (existing.and_then(Value::as_object_mut), object) {
+            (Some(existing), Value::Object(fields)) => {
+                for (key, value) in fields {
+                    existing.entry(key).or_insert(value);
+                }
+            }
+            (_, object) => self.objects.push(object),
+        }
+    }
+}
+
+fn random_id(kind: &str) -> String {
+    format!("{}--{}", kind, Uuid::new_v4())
+}
+
+/// UUIDv5 over the canonical JSON of the id-contributing properties.
+fn observable_id(kind: &str, contributing: &str) -> String {
+    format!(
+        "{}--{}",
+        kind,
+        Uuid::new_v5(&SCO_NAMESPACE, contributing.as_bytes())
+    )
+}
+
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
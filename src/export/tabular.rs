@@ -0,0 +1,211 @@
+//! Row-oriented exporters (JSON lines, CSV, terminal table, Markdown).
+
+use std::fmt::Write;
+
+use crate::{
+    core::{engine::ReconResult, error::FalconError},
+    export::csv_field,
+    modules::diff::provider_states,
+};
+
+/// One provider's outcome for one target.
+pub(crate) struct Row<'a> {
+    pub target: &'a str,
+    pub kind: &'static str,
+    pub provider: String,
+    pub status: &'static str,
+    pub url: &'a str,
+    pub cached: bool,
+    pub detail: &'a str,
+}
+
+/// Rows per provider that reported something, sorted by provider; a target
+/// with no accounts, blocks or failures gets a single `none` row.
+pub(crate) fn rows(result: &ReconResult) -> Vec<Row<'_>> {
+    let kind = result.kind.as_str();
+    let states = provider_states(result);
+    if states.is_empty() {
+        return vec![Row {
+            target: &result.target,
+            kind,
+            provider: String::new(),
+            status: "none",
+            url: "",
+            cached: false,
+            detail: "",
+        }];
+    }
+    states
+        .into_iter()
+        .map(|(provider, state)| Row {
+            target: &result.target,
+            kind,
+            url: result
+                .profiles
+                .iter()
+                .find(|p| p.provider == provider)
+                .map_or("", |p| p.url.as_str()),
+            cached: result.cached.contains(&provider),
+            detail: result
+                .failed
+                .iter()
+                .filter_map(|f| f.split_once(':'))
+                .find(|(p, _)| *p == provider)
+                .map_or("", |(_, reason)| reason.trim()),
+            status: state.as_str(),
+            provider,
+        })
+        .collect()
+}
+
+/// One compact `ReconResult` object per line.
+pub fn to_jsonl(results: &[ReconResult]) -> Result<String, FalconError> {
+    let mut out = String::new();
+    for result in results {
+        out.push_str(&serde_json::to_string(result).map_err(|_| FalconError::Unknown)?);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// One row per provider per target.
+pub fn to_csv(results: &[ReconResult]) -> String {
+    let mut out = String::from("target,kind,provider,status,url,cached,detail\n");
+    for row in results.iter().flat_map(rows) {
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{},{}",
+            csv_field(row.target),
+            row.kind,
+            csv_field(&row.provider),
+            row.status,
+            csv_field(row.url),
+            row.cached,
+            csv_field(row.detail)
+        );
+    }
+    out
+}
+
+/// Aligned columns for reading in a terminal, with a hit count per target.
+pub fn to_table(results: &[ReconResult]) -> String {
+    const HEADER: [&str; 5] = ["TARGET", "KIND", "PROVIDER", "STATUS", "URL"];
+    let cells: Vec<[String; 5]> = results
+        .iter()
+        .flat_map(rows)
+        .map(|row| {
+            let status = match (row.cached, row.detail) {
+                (true, _) => format!("{} (cached)", row.status),
+                (false, "") => row.status.to_string(),
+                (false, detail) => format!("{} ({})", row.status, detail),
+            };
+            [
+                row.target.to_string(),
+                row.kind.to_string(),
+                if row.provider.is_empty() {
+                    "-".to_string()
+                } else {
+                    row.provider
+                },
+                status,
+                row.url.to_string(),
+            ]
+        })
+        .collect();
+    let mut widths = HEADER.map(|h| h.chars().count());
+    for row in &cells {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut out = String::new();
+    let mut line = |cols: [&str; 5]| {
+        let mut text = String::new();
+        for (i, (col, width)) in cols.iter().zip(widths).enumerate() {
+            if i + 1 < cols.len() {
+                let _ = write!(text, "{:<width$}  ", col, width = width);
+            } else {
+                text.push_str(col);
+            }
+        }
+        out.push_str(text.trim_end());
+        out.push('\n');
+    };
+    line(HEADER);
+    for row in &cells {
+        line([&row[0], &row[1], &row[2], &row[3], &row[4]]);
+    }
+    let hits: usize = results.iter().map(|r| r.hits).sum();
+    let _ = writeln!(
+        out,
+        "\n{} target{}, {} hit{}",
+        results.len(),
+        if results.len() == 1 { "" } else { "s" },
+        hits,
+        if hits == 1 { "" } else { "s" }
+    );
+    out
+}
+
+/// A section per target with a summary line and a provider table.
+pub fn to_markdown(results: &[ReconResult]) -> String {
+    let mut out = String::from("# BLOODY-F4LCON results\n");
+    for result in results {
+        let _ = writeln!(
+            out,
+            "\n## {} ({})\n",
+            md_cell(&result.target),
+            result.kind.as_str()
+        );
+        if let Some(provenance) = &result.provenance {
+            let _ = writeln!(
+                out,
+                "Found via {} on `{}` (depth {}).\n",
+                md_cell(&provenance.provider),
+                provenance.parent,
+                provenance.depth
+            );
+        }
+        let _ = writeln!(
+            out,
+            "**{}** hits · {} restricted · {} rate-limited · {} failed\n",
+            result.hits,
+            result.restricted.len(),
+            result.rate_limited.len(),
+            result.failed.len()
+        );
+        let rows = rows(result);
+        if rows.iter().all(|row| row.provider.is_empty()) {
+            out.push_str("_No accounts found._\n");
+            continue;
+        }
+        out.push_str("| Provider | Status | URL | Detail |\n| --- | --- | --- | --- |\n");
+        for row in rows {
+            let status = if row.cached {
+                format!("{} (cached)", row.status)
+            } else {
+                row.status.to_string()
+            };
+            let url = if row.url.is_empty() {
+                String::new()
+            } else {
+                format!("<{}>", row.url)
+            };
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} |",
+                md_cell(&row.provider),
+                status,
+                url,
+                md_cell(row.detail)
+            );
+        }
+    }
+    out
+}
+
+/// Keep a value inside its table cell.
+pub(crate) fn md_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace(['\n', '\r'], " ")
+}
@@ -0,0 +1,55 @@
+//! Timeline exporters (CSV, JSON).
+
+use std::fmt::Write;
+
+use chrono::{SecondsFormat, Utc};
+
+use crate::{
+    core::{engine::ReconResult, error::FalconError},
+    export::csv_field,
+    modules::timeline::{ActivityWindow, Timeline},
+};
+
+fn timelines(results: &[ReconResult]) -> Vec<Timeline> {
+    let now = Utc::now();
+    results
+        .iter()
+        .map(|r| Timeline::build(r, now, ActivityWindow::default()))
+        .collect()
+}
+
+/// One row per timeline event, oldest first within each target.
+pub fn to_csv(results: &[ReconResult]) -> String {
+    let mut out = String::from("target,at,provider,event,activity,url\n");
+    for timeline in timelines(results) {
+        for event in &timeline.events {
+            let activity = timeline
+                .accounts
+                .iter()
+                .find(|a| a.provider == event.provider)
+                .map_or("unknown", |a| a.activity.as_str());
+            let _ = writeln!(
+                out,
+                "{},{},{},{},{},{}",
+                csv_field(&timeline.target),
+                event.at.to_rfc3339_opts(SecondsFormat::Secs, true),
+                csv_field(&event.provider),
+                event.kind.as_str(),
+                activity,
+                csv_field(&event.url)
+            );
+        }
+    }
+    out
+}
+
+/// Timeline object per target (array for several targets).
+pub fn to_json(results: &[ReconResult]) -> Result<String, FalconError> {
+    let timelines = timelines(results);
+    let json = if let [single] = timelines.as_slice() {
+        serde_json::to_string_pretty(single)
+    } else {
+        serde_json::to_string_pretty(&timelines)
+    };
+    json.map_err(|_| FalconError::Unknown)
+}
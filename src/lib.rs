@@ -1,6 +1,8 @@
 //! Library entry points for modular extensions.
 
+pub mod commands;
 pub mod config;
 pub mod core;
+pub mod export;
 pub mod modules;
 pub mod ui;
@@ -1,12 +1,38 @@
 use std::sync::Arc;
-use std::{fs, path::Path};
+use std::{fs, io::IsTerminal, path::Path, process::ExitCode};
 
 use bloody_falcon::{
+    commands::{
+        cache::{self as cache_cmd, CacheCommand},
+        case::{self as case_cmd, CaseCommand},
+        diff::{self as diff_cmd, DiffArgs},
+        evidence::{self as evidence_cmd, EvidenceCommand},
+        rekey::{self as rekey_cmd, RekeyArgs},
+        watch::{self as watch_cmd, WatchArgs},
+    },
     config::{apply_provider_filter, load_config},
-    core::{engine::Engine, error::FalconError},
+    core::{
+        case::Case,
+        crypto::{self, KeySource},
+        disk_cache::DiskCache,
+        engine::{Engine, TargetKind},
+        error::FalconError,
+        evidence::EvidenceVault,
+        exit::{self, ExitStatus, FailOn},
+        logger::LogFeed,
+    },
+    export::{
+        self,
+        envelope::{Envelope, RequestedTarget},
+        html, notes,
+        siem::SiemSinks,
+        Format,
+    },
+    modules::crosslink::pivot::PivotPolicy,
     ui::{app::App, terminal::run_tui},
 };
-use clap::Parser;
+use chrono::Utc;
+use clap::{Parser, Subcommand};
 use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
 #[derive(Parser, Debug)]
@@ -15,8 +41,10 @@ use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, Env
     about = "OSINT terminal recon with live provider checks"
 )]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
     /// Path to config file (TOML). Default: config/bloodyf4lcon.toml
-    #[arg(long)]
+    #[arg(long, global = true)]
     config: Option<String>,
     /// Comma-separated provider names to enable (case-insensitive)
     #[arg(long, value_delimiter = ',')]
@@ -24,33 +52,97 @@ struct Cli {
     /// Disable in-memory cache
     #[arg(long)]
     no_cache: bool,
-    /// Optional initial target
+    /// Optional initial target (`domain:example.com` for a domain)
     target: Option<String>,
+    /// File with one target per line (blank lines and `#` comments skipped)
+    #[arg(long)]
+    targets_file: Option<String>,
+    /// Saved headless JSON output (results, JSON lines or an envelope) to load as targets
+    #[arg(long)]
+    load: Vec<String>,
+    /// Type of the initial target
+    #[arg(long, value_enum)]
+    target_type: Option<TargetKind>,
     /// Optional label for the initial target
     #[arg(long)]
     label: Option<String>,
     /// Increase verbosity (info, debug)
-    #[arg(short, long, action = clap::ArgAction::Count)]
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
     verbose: u8,
     /// Optional log file path
-    #[arg(long, default_value = "data/falcon.log")]
+    #[arg(long, global = true, default_value = "data/falcon.log")]
     log_file: String,
-    /// Run without TUI; print JSON result to stdout
+    /// Run without TUI; print result to stdout
     #[arg(long)]
     no_tui: bool,
+    /// Headless output format
+    #[arg(long, value_enum, default_value_t = Format::Json)]
+    format: Format,
+    /// Exit with the fatal code (2) when any check ends this way
+    #[arg(long, value_enum, value_delimiter = ',')]
+    fail_on: Vec<FailOn>,
+    /// Write headless output to this file instead of stdout
+    #[arg(long)]
+    output: Option<String>,
+    /// Queue identifiers discovered during scans as new targets
+    #[arg(long)]
+    pivot: bool,
+    /// Maximum pivot depth (1 = identifiers found on the initial target)
+    #[arg(long, default_value_t = PivotPolicy::default().max_depth)]
+    pivot_depth: usize,
+    /// Maximum number of targets in a pivot run, initial target included
+    #[arg(long, default_value_t = PivotPolicy::default().max_targets)]
+    pivot_max_targets: usize,
     /// Enable persistent disk cache
     #[arg(long)]
     disk_cache: bool,
-    /// Path for disk cache (JSON/SQLite placeholder)
-    #[arg(long)]
+    /// Path for disk cache log (a legacy JSON cache there is migrated)
+    #[arg(long, global = true)]
     disk_cache_path: Option<String>,
+    /// Record targets and scan results to this case (see `case new`)
+    #[arg(long)]
+    case: Option<String>,
+    /// Key file encrypting the disk cache and saved files (else $BLOODY_FALCON_PASSPHRASE)
+    #[arg(long, global = true)]
+    key_file: Option<String>,
+    /// Keep hashed captures of provider responses for hits (see `evidence`)
+    #[arg(long, global = true)]
+    evidence: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Inspect, prune and share the disk cache
+    #[command(subcommand)]
+    Cache(CacheCommand),
+    /// Create, inspect and annotate case workspaces
+    #[command(subcommand)]
+    Case(CaseCommand),
+    /// List, show, verify and prune evidence captures
+    #[command(subcommand)]
+    Evidence(EvidenceCommand),
+    /// Compare two scans of a target (case history, cache vs live, or result files)
+    Diff(DiffArgs),
+    /// Rescan targets on a schedule and alert when accounts appear or vanish
+    Watch(WatchArgs),
+    /// Move the cache, cases, watch state, evidence and saved files to a new key
+    Rekey(RekeyArgs),
 }
 
 #[tokio::main]
-async fn main() -> Result<(), FalconError> {
+async fn main() -> ExitCode {
     let cli = Cli::parse();
+    match run(cli).await {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitStatus::Fatal.into()
+        }
+    }
+}
 
-    init_tracing(&cli)?;
+async fn run(cli: Cli) -> Result<ExitCode, FalconError> {
+    let log_feed = init_tracing(&cli)?;
 
     let mut cfg = load_config(cli.config.as_deref())?;
     cfg = apply_provider_filter(cfg, cli.providers.as_deref());
@@ -61,30 +153,224 @@ async fn main() -> Result<(), FalconError> {
         cfg.disk_cache_enabled = true;
         cfg.disk_cache_path = path;
     }
+    if let Some(path) = cli.key_file {
+        cfg.key_file = Some(path);
+    }
+    if cli.evidence {
+        cfg.evidence_enabled = true;
+    }
+    if let Some(Command::Cache(command)) = &cli.command {
+        let cache = DiskCache::open(
+            Path::new(&cfg.disk_cache_path),
+            KeySource::resolve(cfg.key_file.as_deref()),
+        )?;
+        print!("{}", cache_cmd::run(command, &cache, &cfg)?);
+        return Ok(ExitCode::SUCCESS);
+    }
+    if let Some(Command::Evidence(command)) = &cli.command {
+        let vault = EvidenceVault::new(&cfg, KeySource::resolve(cfg.key_file.as_deref()));
+        print!("{}", evidence_cmd::run(command, &vault)?);
+        return Ok(ExitCode::SUCCESS);
+    }
+    if let Some(Command::Case(command)) = &cli.command {
+        print!("{}", case_cmd::run(command, &cfg)?);
+        return Ok(ExitCode::SUCCESS);
+    }
+    if let Some(Command::Rekey(args)) = &cli.command {
+        print!("{}", rekey_cmd::run(args, &cfg)?);
+        return Ok(ExitCode::SUCCESS);
+    }
+    let mut case = match &cli.case {
+        Some(name) => Some(Case::open(
+            Path::new(&cfg.case_dir),
+            name,
+            KeySource::resolve(cfg.key_file.as_deref()),
+        )?),
+        None => None,
+    };
+    if let Some(Command::Diff(args)) = &cli.command {
+        let engine = Engine::new(cfg.clone())?;
+        print!("{}", diff_cmd::run(args, &engine, &cfg).await?);
+        return Ok(ExitCode::SUCCESS);
+    }
+    if let Some(Command::Watch(args)) = &cli.command {
+        let engine = Engine::new(cfg.clone())?;
+        watch_cmd::run(args, &engine, &cfg).await?;
+        return Ok(ExitCode::SUCCESS);
+    }
     let engine = Arc::new(Engine::new(cfg)?);
     let mut app = App::new();
     if let Some(initial) = cli.target {
-        app.add_target_with_label(initial, cli.label);
+        let (kind, id) = match cli.target_type {
+            Some(kind) => (kind, initial),
+            None => TargetKind::parse_input(&initial),
+        };
+        app.add_typed_target(kind, id, cli.label.clone());
+    }
+    if let Some(path) = &cli.targets_file {
+        let list =
+            fs::read_to_string(path).map_err(|e| FalconError::Config(format!("{path}: {e}")))?;
+        for line in list.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (kind, id) = TargetKind::parse_input(line);
+            app.add_typed_target(kind, id, None);
+        }
+    }
+    for path in &cli.load {
+        let key = KeySource::resolve(engine.config.key_file.as_deref());
+        app.load_saved(Path::new(path), key.as_ref())?;
     }
     let use_cache = !cli.no_cache;
+    let pivot = cli.pivot.then_some(PivotPolicy {
+        max_depth: cli.pivot_depth,
+        max_targets: cli.pivot_max_targets,
+    });
 
     if cli.no_tui {
         if app.targets.is_empty() {
             return Err(FalconError::Config(
-                "no target provided for headless run; pass a target".into(),
+                "no target provided for headless run; pass a target or --targets-file".into(),
             ));
         }
-        let target = &app.targets[0].id.clone();
-        let result = engine.scan_username(target, use_cache).await?;
-        let json = serde_json::to_string_pretty(&result).map_err(|_| FalconError::Unknown)?;
-        println!("{json}");
-        Ok(())
+        let notes_dir = match (cli.format, cli.output.as_deref()) {
+            (Format::Notes, None) => {
+                return Err(FalconError::Config(
+                    "--format notes writes one file per target; pass --output <dir>".into(),
+                ))
+            }
+            (Format::Notes, Some(dir)) => Some(dir),
+            _ => None,
+        };
+        let batch = app.targets.len() > 1;
+        let started_at = Utc::now();
+        let mut results = Vec::new();
+        // Per target: why its scan was skipped, if it was.
+        let mut errors = vec![None; app.targets.len()];
+        for (target, error) in app.targets.iter().zip(errors.iter_mut()) {
+            let scanned = match pivot {
+                Some(policy) => {
+                    engine
+                        .scan_with_pivots(target.kind, &target.id, use_cache, policy)
+                        .await
+                }
+                None => engine
+                    .scan_target(target.kind, &target.id, use_cache)
+                    .await
+                    .map(|result| vec![result]),
+            };
+            let scanned = match scanned {
+                Ok(scanned) => scanned,
+                // One unreachable target should not sink a whole batch.
+                Err(e) if batch => {
+                    tracing::warn!("scan of {} failed: {}", target.id, e);
+                    *error = Some(e.to_string());
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            if let Some(case) = case.as_mut() {
+                case.add_target(target.kind, &target.id, target.label.clone())?;
+                for result in &scanned {
+                    case.record_scan(result)?;
+                }
+            }
+            results.extend(scanned);
+        }
+        if let Some(siem) = SiemSinks::from_config(&engine.config) {
+            siem.send(&results, Utc::now()).await;
+        }
+        let skipped = errors.iter().flatten().count();
+        let status = exit::classify(&results, skipped, &cli.fail_on);
+        if let Some(dir) = notes_dir {
+            let labels = app
+                .targets
+                .iter()
+                .filter_map(|t| Some((t.id.clone(), t.label.clone()?)))
+                .collect();
+            if KeySource::resolve(engine.config.key_file.as_deref()).is_some() {
+                tracing::warn!(
+                    "notes in {} are written in plaintext; the key does not cover them",
+                    dir
+                );
+            }
+            let written = notes::write_notes(&results, &labels, Path::new(dir), Utc::now())?;
+            for path in written {
+                println!("{}", path.display());
+            }
+            return Ok(status.into());
+        }
+        let mut rendered = match cli.format {
+            Format::Json => {
+                let targets = app
+                    .targets
+                    .iter()
+                    .zip(errors)
+                    .map(|(t, error)| RequestedTarget {
+                        target: t.id.clone(),
+                        kind: t.kind,
+                        label: t.label.clone(),
+                        error,
+                    })
+                    .collect();
+                let envelope =
+                    Envelope::new(&engine.config, targets, use_cache, started_at, results);
+                serde_json::to_string_pretty(&envelope).map_err(|_| FalconError::Unknown)?
+            }
+            Format::Html => {
+                let template = html::load_template(Path::new(&engine.config.report_template))?;
+                let meta = html::ReportMeta {
+                    providers: engine
+                        .config
+                        .providers
+                        .iter()
+                        .filter(|p| p.enabled)
+                        .map(|p| p.name.clone())
+                        .collect(),
+                    ..html::ReportMeta::default()
+                };
+                let meta = match &cli.case {
+                    Some(name) => html::ReportMeta {
+                        title: format!("Case {name}"),
+                        ..meta
+                    },
+                    None => meta,
+                };
+                html::to_html(&results, &template, &meta)
+            }
+            format => export::render(format, &results)?,
+        };
+        if !rendered.ends_with('\n') {
+            rendered.push('\n');
+        }
+        match &cli.output {
+            Some(path) => crypto::write_file(
+                Path::new(path),
+                rendered.as_bytes(),
+                KeySource::resolve(engine.config.key_file.as_deref()).as_ref(),
+            )?,
+            None => print!("{rendered}"),
+        }
+        Ok(status.into())
     } else {
-        run_tui(engine, app, use_cache).await
+        if let Some(case) = case {
+            app.attach_case(case);
+        }
+        if let Some(policy) = pivot {
+            app.enable_pivot(policy);
+        }
+        if let Some(feed) = log_feed {
+            app.attach_log_feed(feed);
+        }
+        run_tui(engine, app, use_cache).await?;
+        Ok(ExitCode::SUCCESS)
     }
 }
 
-fn init_tracing(cli: &Cli) -> Result<(), FalconError> {
+/// Logs go to the log file plus stderr, so stdout carries only results; in
+/// the TUI they go to the log panel instead, whose feed is returned.
+fn init_tracing(cli: &Cli) -> Result<Option<LogFeed>, FalconError> {
     let level = match cli.verbose {
         0 => "info",
         1 => "debug",
@@ -116,12 +402,28 @@ fn init_tracing(cli: &Cli) -> Result<(), FalconError> {
         .with_ansi(false)
         .with_target(false);
 
-    let stdout_layer = fmt::layer().with_writer(std::io::stdout).with_target(false);
+    let tui = cli.command.is_none() && !cli.no_tui;
+    let feed = tui.then(LogFeed::new);
+    let panel_layer = feed.clone().map(|feed| {
+        fmt::layer()
+            .with_writer(feed)
+            .with_ansi(false)
+            .with_target(false)
+            .without_time()
+    });
+    let stderr_layer = (!tui).then(|| {
+        fmt::layer()
+            .with_writer(std::io::stderr)
+            .with_ansi(std::io::stderr().is_terminal())
+            .with_target(false)
+    });
 
     tracing_subscriber::registry()
         .with(filter)
         .with(file_layer)
-        .with(stdout_layer)
+        .with(panel_layer)
+        .with(stderr_layer)
         .try_init()
-        .map_err(|e| FalconError::Config(e.to_string()))
+        .map_err(|e| FalconError::Config(e.to_string()))?;
+    Ok(feed)
 }
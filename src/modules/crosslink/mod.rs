@@ -1 +1,162 @@
-//! Cross-linking module placeholder.
+//! Cross-linking module: identifiers discovered on profile pages.
+
+pub mod pivot;
+pub mod similarity;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::ProviderConfig;
+
+/// Path segments that follow a provider host but are not usernames.
+const RESERVED_SEGMENTS: &[&str] = &[
+    "about", "explore", "features", "hashtag", "help", "home", "i", "intent", "login", "logout",
+    "privacy", "search", "settings", "share", "signup", "terms",
+];
+
+/// File extensions that look like email TLDs in asset names (`logo@2x.png`).
+const ASSET_SUFFIXES: &[&str] = &["png", "jpg", "jpeg", "gif", "svg", "webp", "css", "js"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IdentifierKind {
+    Username,
+    Email,
+    Domain,
+}
+
+/// Identifier found on a profile page that points at another account.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LinkedIdentifier {
+    pub kind: IdentifierKind,
+    pub value: String,
+    /// Provider the identifier belongs to, for usernames.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+}
+
+/// Extract emails and usernames on known providers from a profile page.
+///
+/// `source` is the provider the page was fetched from; links back to the
+/// scanned `username` on that same provider are ignored.
+pub fn extract_identifiers(
+    body: &str,
+    source: &str,
+    username: &str,
+    providers: &[ProviderConfig],
+) -> Vec<LinkedIdentifier> {
+    let mut found: Vec<LinkedIdentifier> = Vec::new();
+    let mut push = |ident: LinkedIdentifier| {
+        if !found.contains(&ident) {
+            found.push(ident);
+        }
+    };
+
+    for provider in providers {
+        let Some(needle) = link_prefix(&provider.base_url) else {
+            continue;
+        };
+        for value in usernames_after(body, &needle) {
+            if provider.name == source && value.eq_ignore_ascii_case(username) {
+                continue;
+            }
+            push(LinkedIdentifier {
+                kind: IdentifierKind::Username,
+                value,
+                provider: Some(provider.name.clone()),
+            });
+        }
+    }
+
+    for email in emails_in(body) {
+        push(LinkedIdentifier {
+            kind: IdentifierKind::Email,
+            value: email,
+            provider: None,
+        });
+    }
+
+    found
+}
+
+/// Host + path prefix preceding `{username}`, without scheme or `www.`.
+fn link_prefix(base_url: &str) -> Option<String> {
+    let (prefix, _) = base_url.split_once("{username}")?;
+    let prefix = prefix
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("www.");
+    if prefix.is_empty() || !prefix.contains('/') {
+        return None;
+    }
+    Some(prefix.to_ascii_lowercase())
+}
+
+fn is_username_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.')
+}
+
+fn usernames_after(body: &str, needle: &str) -> Vec<String> {
+    let haystack = body.to_ascii_lowercase();
+    let mut out = Vec::new();
+    let mut from = 0;
+    while let Some(pos) = haystack[from..].find(needle) {
+        let start = from + pos;
+        from = start + needle.len();
+        // Reject subdomains and lookalike hosts (`gist.github.com`, `notgithub.com`).
+        let host_start = haystack[..start]
+            .strip_suffix("www.")
+            .map_or(start, |s| s.len());
+        if haystack[..host_start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+        {
+            continue;
+        }
+        let value: String = body[from..]
+            .chars()
+            .take_while(|c| is_username_char(*c))
+            .collect();
+        let value = value.trim_end_matches('.').to_string();
+        if value.is_empty()
+            || RESERVED_SEGMENTS.contains(&value.to_ascii_lowercase().as_str())
+            || out.contains(&value)
+        {
+            continue;
+        }
+        out.push(value);
+    }
+    out
+}
+
+fn emails_in(body: &str) -> Vec<String> {
+    let bytes = body.as_bytes();
+    let mut out: Vec<String> = Vec::new();
+    for (at, _) in body.match_indices('@') {
+        let local_start = bytes[..at]
+            .iter()
+            .rposition(|b| !(b.is_ascii_alphanumeric() || b"._%+-".contains(b)))
+            .map_or(0, |p| p + 1);
+        let domain_end = bytes[at + 1..]
+            .iter()
+            .position(|b| !(b.is_ascii_alphanumeric() || b".-".contains(b)))
+            .map_or(bytes.len(), |p| at + 1 + p);
+        let local = body[local_start..at].trim_start_matches('.');
+        let domain = body[at + 1..domain_end].trim_end_matches('.');
+        let Some((_, tld)) = domain.rsplit_once('.') else {
+            continue;
+        };
+        if local.is_empty()
+            || tld.len() < 2
+            || !tld.chars().all(|c| c.is_ascii_alphabetic())
+            || ASSET_SUFFIXES.contains(&tld.to_ascii_lowercase().as_str())
+        {
+            continue;
+        }
+        let email = format!("{}@{}", local, domain).to_ascii_lowercase();
+        if !out.contains(&email) {
+            out.push(email);
+        }
+    }
+    out
+}
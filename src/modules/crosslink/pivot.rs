@@ -0,0 +1,146 @@
+//! Recursive pivoting: queue identifiers discovered during a scan as targets.
+
+use std::collections::{HashSet, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::engine::{ReconResult, TargetKind},
+    modules::crosslink::{IdentifierKind, LinkedIdentifier},
+};
+
+/// Shared mailbox names that say nothing about the account owner.
+const ROLE_MAILBOXES: &[&str] = &[
+    "admin", "contact", "hello", "help", "info", "noreply", "no-reply", "support",
+];
+
+/// Where a derived target came from.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// Target whose scan revealed this one.
+    pub parent: String,
+    /// Provider whose profile page carried the identifier.
+    pub provider: String,
+    /// Identifier as found; for emails the target is the local part.
+    pub identifier: LinkedIdentifier,
+    /// Pivot depth, 1 for identifiers found on the root target.
+    pub depth: usize,
+}
+
+/// Limits for a pivot run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PivotPolicy {
+    /// Deepest derived generation that is still scanned.
+    pub max_depth: usize,
+    /// Total targets in the run, roots included.
+    pub max_targets: usize,
+}
+
+impl Default for PivotPolicy {
+    fn default() -> Self {
+        Self {
+            max_depth: 2,
+            max_targets: 25,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PivotTarget {
+    pub kind: TargetKind,
+    pub username: String,
+    pub provenance: Option<Provenance>,
+}
+
+/// Breadth-first queue of targets with loop detection and caps.
+#[derive(Clone, Debug)]
+pub struct PivotQueue {
+    policy: PivotPolicy,
+    queue: VecDeque<PivotTarget>,
+    seen: HashSet<String>,
+}
+
+impl PivotQueue {
+    pub fn new(policy: PivotPolicy) -> Self {
+        Self {
+            policy,
+            queue: VecDeque::new(),
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Queue a user-supplied target. Returns false if it was already seen.
+    pub fn push_root(&mut self, kind: TargetKind, username: &str) -> bool {
+        if !self.mark_seen(username) {
+            return false;
+        }
+        self.queue.push_back(PivotTarget {
+            kind,
+            username: username.to_string(),
+            provenance: None,
+        });
+        true
+    }
+
+    /// Record a target as known without queueing it.
+    pub fn mark_seen(&mut self, username: &str) -> bool {
+        self.seen.insert(username.to_lowercase())
+    }
+
+    pub fn pop(&mut self) -> Option<PivotTarget> {
+        self.queue.pop_front()
+    }
+
+    /// Queue new identifiers from a finished scan; returns what was accepted.
+    ///
+    /// The result's own `provenance` determines the depth of its children.
+    pub fn offer(&mut self, result: &ReconResult) -> Vec<PivotTarget> {
+        let depth = result.provenance.as_ref().map_or(0, |p| p.depth) + 1;
+        if depth > self.policy.max_depth {
+            return Vec::new();
+        }
+        let mut accepted = Vec::new();
+        for profile in &result.profiles {
+            for linked in &profile.linked {
+                if self.seen.len() >= self.policy.max_targets {
+                    return accepted;
+                }
+                let Some(username) = pivot_username(linked) else {
+                    continue;
+                };
+                if !self.mark_seen(&username) {
+                    continue;
+                }
+                let target = PivotTarget {
+                    kind: TargetKind::Username,
+                    username,
+                    provenance: Some(Provenance {
+                        parent: result.target.clone(),
+                        provider: profile.provider.clone(),
+                        identifier: linked.clone(),
+                        depth,
+                    }),
+                };
+                self.queue.push_back(target.clone());
+                accepted.push(target);
+            }
+        }
+        accepted
+    }
+}
+
+/// Username to scan for a linked identifier, if it yields one.
+pub fn pivot_username(linked: &LinkedIdentifier) -> Option<String> {
+    match linked.kind {
+        IdentifierKind::Username => Some(linked.value.clone()),
+        IdentifierKind::Email => {
+            let (local, _) = linked.value.split_once('@')?;
+            let local = local.split('+').next().unwrap_or(local);
+            if local.len() < 3 || ROLE_MAILBOXES.contains(&local) {
+                return None;
+            }
+            Some(local.to_string())
+        }
+        IdentifierKind::Domain => None,
+    }
+}
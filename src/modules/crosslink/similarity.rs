@@ -0,0 +1,149 @@
+//! "Same person" scoring across the profiles a target hit.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{core::engine::ProfileHit, modules::recon::metadata::ProfileMetadata};
+
+const NAME_WEIGHT: f64 = 0.5;
+const BIO_WEIGHT: f64 = 0.3;
+const LOCATION_WEIGHT: f64 = 0.2;
+
+/// Likelihood that two hits belong to the same person.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AccountSimilarity {
+    pub a: String,
+    pub b: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bio: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<f64>,
+    /// Weighted mean of the fields both profiles expose, 0.0..=1.0.
+    pub score: f64,
+}
+
+/// Banded reading of an [`AccountSimilarity`] score.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    LikelySame,
+    Possible,
+    Unlikely,
+}
+
+impl Verdict {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Verdict::LikelySame => "likely same",
+            Verdict::Possible => "possible",
+            Verdict::Unlikely => "unlikely",
+        }
+    }
+}
+
+impl AccountSimilarity {
+    pub fn verdict(&self) -> Verdict {
+        if self.score >= 0.8 {
+            Verdict::LikelySame
+        } else if self.score >= 0.5 {
+            Verdict::Possible
+        } else {
+            Verdict::Unlikely
+        }
+    }
+}
+
+/// Score every pair of profiles where both sides have comparable metadata.
+pub fn score_profiles(profiles: &[ProfileHit]) -> Vec<AccountSimilarity> {
+    let mut out = Vec::new();
+    for (i, a) in profiles.iter().enumerate() {
+        for b in &profiles[i + 1..] {
+            let (Some(ma), Some(mb)) = (&a.metadata, &b.metadata) else {
+                continue;
+            };
+            if let Some(similarity) = compare(&a.provider, ma, &b.provider, mb) {
+                out.push(similarity);
+            }
+        }
+    }
+    out
+}
+
+fn compare(
+    a: &str,
+    ma: &ProfileMetadata,
+    b: &str,
+    mb: &ProfileMetadata,
+) -> Option<AccountSimilarity> {
+    let display_name = both(&ma.display_name, &mb.display_name).map(|(x, y)| name_score(x, y));
+    let bio = both(&ma.bio, &mb.bio).map(|(x, y)| token_overlap(x, y));
+    let location =
+        both(&ma.location, &mb.location).map(|(x, y)| name_score(x, y).max(token_overlap(x, y)));
+
+    let weighted = [
+        (display_name, NAME_WEIGHT),
+        (bio, BIO_WEIGHT),
+        (location, LOCATION_WEIGHT),
+    ];
+    let total: f64 = weighted.iter().filter_map(|(s, w)| s.map(|_| *w)).sum();
+    if total == 0.0 {
+        return None;
+    }
+    let score = weighted
+        .iter()
+        .filter_map(|(s, w)| s.map(|s| s * w))
+        .sum::<f64>()
+        / total;
+
+    Some(AccountSimilarity {
+        a: a.to_string(),
+        b: b.to_string(),
+        display_name,
+        bio,
+        location,
+        score: round(score),
+    })
+}
+
+fn both<'a>(x: &'a Option<String>, y: &'a Option<String>) -> Option<(&'a str, &'a str)> {
+    Some((x.as_deref()?, y.as_deref()?))
+}
+
+fn normalize(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Jaro-Winkler on normalized strings, 0.0..=1.0.
+pub fn name_score(a: &str, b: &str) -> f64 {
+    round(strsim::jaro_winkler(&normalize(a), &normalize(b)))
+}
+
+/// Jaccard overlap of word tokens (3+ chars), 0.0..=1.0.
+pub fn token_overlap(a: &str, b: &str) -> f64 {
+    let tokens = |s: &str| -> HashSet<String> {
+        normalize(s)
+            .split_whitespace()
+            .filter(|t| t.chars().count() >= 3)
+            .map(str::to_string)
+            .collect()
+    };
+    let (ta, tb) = (tokens(a), tokens(b));
+    let union = ta.union(&tb).count();
+    if union == 0 {
+        return 0.0;
+    }
+    round(ta.intersection(&tb).count() as f64 / union as f64)
+}
+
+fn round(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
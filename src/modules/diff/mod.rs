@@ -0,0 +1,212 @@
+//! Compare two scans of the same target, provider by provider.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::core::{
+    engine::{ReconResult, TargetKind},
+    error::FalconError,
+};
+
+/// What a scan recorded for one provider. Providers absent from a result
+/// (no account, or not checked) have no state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderState {
+    Hit,
+    Restricted,
+    RateLimited,
+    Failed,
+}
+
+impl ProviderState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProviderState::Hit => "hit",
+            ProviderState::Restricted => "restricted",
+            ProviderState::RateLimited => "rate_limited",
+            ProviderState::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// Now a hit; absent before.
+    Appeared,
+    /// Was a hit; now absent.
+    Disappeared,
+    /// Any other state change, including to or from a failed, restricted or
+    /// rate-limited check, which says nothing about the account itself.
+    Changed,
+}
+
+impl ChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeKind::Appeared => "appeared",
+            ChangeKind::Disappeared => "disappeared",
+            ChangeKind::Changed => "changed",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ProviderChange {
+    pub provider: String,
+    pub change: ChangeKind,
+    pub before: Option<ProviderState>,
+    pub after: Option<ProviderState>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ScanDiff {
+    pub target: String,
+    pub kind: TargetKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_at: Option<DateTime<Utc>>,
+    pub changes: Vec<ProviderChange>,
+    /// Providers in the same non-empty state in both scans.
+    pub unchanged: Vec<String>,
+}
+
+impl ScanDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn appeared(&self) -> impl Iterator<Item = &ProviderChange> {
+        self.changes
+            .iter()
+            .filter(|c| c.change == ChangeKind::Appeared)
+    }
+
+    pub fn disappeared(&self) -> impl Iterator<Item = &ProviderChange> {
+        self.changes
+            .iter()
+            .filter(|c| c.change == ChangeKind::Disappeared)
+    }
+
+    /// One line per change, `+` appeared, `-` disappeared, `~` changed.
+    pub fn to_text(&self) -> String {
+        let when = |at: Option<DateTime<Utc>>| {
+            at.map_or("?".to_string(), |at| {
+                at.format("%Y-%m-%d %H:%M").to_string()
+            })
+        };
+        let mut out = format!(
+            "{}: {} -> {}\n",
+            self.target,
+            when(self.before_at),
+            when(self.after_at)
+        );
+        if self.changes.is_empty() {
+            out.push_str("  no changes\n");
+        }
+        for line in self.lines() {
+            out.push_str("  ");
+            out.push_str(&line);
+            out.push('\n');
+        }
+        if !self.unchanged.is_empty() {
+            out.push_str(&format!("  unchanged: {}\n", self.unchanged.join(", ")));
+        }
+        out
+    }
+
+    /// Change lines without header, for embedding (e.g. the TUI).
+    pub fn lines(&self) -> Vec<String> {
+        self.changes
+            .iter()
+            .map(|c| {
+                let state = |s: Option<ProviderState>| s.map_or("none", |s| s.as_str());
+                let sign = match c.change {
+                    ChangeKind::Appeared => '+',
+                    ChangeKind::Disappeared => '-',
+                    ChangeKind::Changed => '~',
+                };
+                format!(
+                    "{} {} {} ({} -> {})",
+                    sign,
+                    c.provider,
+                    c.change.as_str(),
+                    state(c.before),
+                    state(c.after)
+                )
+            })
+            .collect()
+    }
+}
+
+/// Per-provider states recorded in `result`.
+pub fn provider_states(result: &ReconResult) -> BTreeMap<String, ProviderState> {
+    let mut states = BTreeMap::new();
+    for failed in &result.failed {
+        let provider = failed.split_once(':').map_or(failed.as_str(), |(p, _)| p);
+        states.insert(provider.to_string(), ProviderState::Failed);
+    }
+    for provider in &result.rate_limited {
+        states.insert(provider.clone(), ProviderState::RateLimited);
+    }
+    for provider in &result.restricted {
+        states.insert(provider.clone(), ProviderState::Restricted);
+    }
+    for provider in &result.platforms {
+        states.insert(provider.clone(), ProviderState::Hit);
+    }
+    states
+}
+
+/// Diff two scans of the same target.
+pub fn diff(
+    before: &ReconResult,
+    after: &ReconResult,
+    before_at: Option<DateTime<Utc>>,
+    after_at: Option<DateTime<Utc>>,
+) -> Result<ScanDiff, FalconError> {
+    if before.target != after.target || before.kind != after.kind {
+        return Err(FalconError::Config(format!(
+            "cannot diff scans of different targets ({} vs {})",
+            before.target, after.target
+        )));
+    }
+    let old = provider_states(before);
+    let new = provider_states(after);
+    let mut providers: Vec<&String> = old.keys().chain(new.keys()).collect();
+    providers.sort();
+    providers.dedup();
+
+    let mut changes = Vec::new();
+    let mut unchanged = Vec::new();
+    for provider in providers {
+        let (was, now) = (old.get(provider).copied(), new.get(provider).copied());
+        let change = match (was, now) {
+            _ if was == now => {
+                unchanged.push(provider.clone());
+                continue;
+            }
+            (None, Some(ProviderState::Hit)) => ChangeKind::Appeared,
+            (Some(ProviderState::Hit), None) => ChangeKind::Disappeared,
+            _ => ChangeKind::Changed,
+        };
+        changes.push(ProviderChange {
+            provider: provider.clone(),
+            change,
+            before: was,
+            after: now,
+        });
+    }
+    Ok(ScanDiff {
+        target: after.target.clone(),
+        kind: after.kind,
+        before_at,
+        after_at,
+        changes,
+        unchanged,
+    })
+}
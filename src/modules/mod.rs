@@ -2,4 +2,7 @@
 
 pub mod breach;
 pub mod crosslink;
+pub mod diff;
 pub mod recon;
+pub mod timeline;
+pub mod watch;
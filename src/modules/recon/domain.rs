@@ -0,0 +1,232 @@
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, Utc};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    core::{error::FalconError, evidence::Capture},
+    modules::recon::metadata::parse_timestamp,
+};
+
+/// Registration and certificate-transparency findings for a domain.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DomainIntel {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registrar: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registered: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_changed: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub status: Vec<String>,
+    #[serde(default)]
+    pub nameservers: Vec<String>,
+    /// Contact emails published in the RDAP record.
+    #[serde(default)]
+    pub emails: Vec<String>,
+    /// Hostnames under the domain seen in CT logs.
+    #[serde(default)]
+    pub hostnames: Vec<String>,
+}
+
+impl DomainIntel {
+    /// Fill gaps from another source's findings.
+    pub fn merge(&mut self, other: DomainIntel) {
+        self.registrar = self.registrar.take().or(other.registrar);
+        self.registered = self.registered.or(other.registered);
+        self.last_changed = self.last_changed.or(other.last_changed);
+        self.expires = self.expires.or(other.expires);
+        for (mine, theirs) in [
+            (&mut self.status, other.status),
+            (&mut self.nameservers, other.nameservers),
+            (&mut self.emails, other.emails),
+            (&mut self.hostnames, other.hostnames),
+        ] {
+            for value in theirs {
+                if !mine.contains(&value) {
+                    mine.push(value);
+                }
+            }
+        }
+    }
+}
+
+/// Result of a single domain endpoint query; a find carries the raw
+/// response for the evidence vault.
+#[derive(Debug, Clone)]
+pub enum Lookup<T> {
+    Found(T, Capture),
+    NotFound,
+    Restricted,
+    RateLimited,
+}
+
+/// Whether `domain` is a plain hostname: dot-separated, non-empty labels of
+/// ASCII letters, digits and hyphens (IDNs in their `xn--` form).
+pub fn is_hostname(domain: &str) -> bool {
+    domain.len() <= 253
+        && domain.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && label
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        })
+}
+
+/// Endpoint URL for `domain`, substituting `{domain}` percent-encoded.
+pub fn endpoint_url(template: &str, domain: &str) -> String {
+    let encoded: String = domain
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect();
+    template.replace("{domain}", &encoded)
+}
+
+/// Query an RDAP server for registration data.
+pub async fn lookup_rdap(
+    client: &Client,
+    template: &str,
+    domain: &str,
+) -> Result<Lookup<DomainIntel>, FalconError> {
+    let (json, capture) = match get_json(client, &endpoint_url(template, domain)).await? {
+        Lookup::Found(json, capture) => (json, capture),
+        Lookup::NotFound => return Ok(Lookup::NotFound),
+        Lookup::Restricted => return Ok(Lookup::Restricted),
+        Lookup::RateLimited => return Ok(Lookup::RateLimited),
+    };
+
+    let mut intel = DomainIntel::default();
+    for event in json["events"].as_array().into_iter().flatten() {
+        let at = event["eventDate"].as_str().and_then(parse_timestamp);
+        match event["eventAction"].as_str() {
+            Some("registration") => intel.registered = at,
+            Some("last changed") => intel.last_changed = at,
+            Some("expiration") => intel.expires = at,
+            _ => {}
+        }
+    }
+    intel.status = json["status"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|s| s.as_str().map(str::to_string))
+        .collect();
+    intel.nameservers = json["nameservers"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|ns| ns["ldhName"].as_str().map(str::to_ascii_lowercase))
+        .collect();
+    collect_entities(&json["entities"], &mut intel);
+    Ok(Lookup::Found(intel, capture))
+}
+
+/// Query a crt.sh-compatible CT search API for hostnames under `domain`.
+pub async fn search_ct(
+    client: &Client,
+    template: &str,
+    domain: &str,
+) -> Result<Lookup<Vec<String>>, FalconError> {
+    let (json, capture) = match get_json(client, &endpoint_url(template, domain)).await? {
+        Lookup::Found(json, capture) => (json, capture),
+        Lookup::NotFound => return Ok(Lookup::NotFound),
+        Lookup::Restricted => return Ok(Lookup::Restricted),
+        Lookup::RateLimited => return Ok(Lookup::RateLimited),
+    };
+    let suffix = format!(".{}", domain.to_ascii_lowercase());
+    let mut hostnames = BTreeSet::new();
+    for entry in json.as_array().into_iter().flatten() {
+        let names = [&entry["name_value"], &entry["common_name"]];
+        for name in names
+            .iter()
+            .filter_map(|v| v.as_str())
+            .flat_map(|v| v.split('\n'))
+        {
+            let name = name.trim().trim_start_matches("*.").to_ascii_lowercase();
+            if name.ends_with(&suffix) || name == domain {
+                hostnames.insert(name);
+            }
+        }
+    }
+    if hostnames.is_empty() {
+        return Ok(Lookup::NotFound);
+    }
+    Ok(Lookup::Found(hostnames.into_iter().collect(), capture))
+}
+
+async fn get_json(client: &Client, url: &str) -> Result<Lookup<Value>, FalconError> {
+    let response = client.get(url).send().await?;
+    match response.status() {
+        StatusCode::NOT_FOUND => Ok(Lookup::NotFound),
+        StatusCode::TOO_MANY_REQUESTS => Ok(Lookup::RateLimited),
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Ok(Lookup::Restricted),
+        status if status.is_success() => {
+            let final_url = response.url().to_string();
+            let headers = response
+                .headers()
+                .iter()
+                .map(|(name, value)| {
+                    (
+                        name.to_string(),
+                        String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                    )
+                })
+                .collect();
+            let body = response.bytes().await?.to_vec();
+            let json = serde_json::from_slice::<Value>(&body)
+                .map_err(|e| FalconError::Provider(e.to_string()))?;
+            Ok(Lookup::Found(
+                json,
+                Capture {
+                    url: url.to_string(),
+                    final_url,
+                    status: status.as_u16(),
+                    headers,
+                    body,
+                    truncated: false,
+                },
+            ))
+        }
+        status => Err(FalconError::Http(status.to_string())),
+    }
+}
+
+/// Walk RDAP entities (nested) for the registrar name and contact emails.
+fn collect_entities(entities: &Value, intel: &mut DomainIntel) {
+    for entity in entities.as_array().into_iter().flatten() {
+        let roles: Vec<&str> = entity["roles"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|r| r.as_str())
+            .collect();
+        for prop in entity["vcardArray"][1].as_array().into_iter().flatten() {
+            let (Some(name), Some(value)) = (prop[0].as_str(), prop[3].as_str()) else {
+                continue;
+            };
+            match name {
+                "fn" if roles.contains(&"registrar") && intel.registrar.is_none() => {
+                    intel.registrar = Some(value.to_string());
+                }
+                "email" if !roles.contains(&"registrar") && !roles.contains(&"abuse") => {
+                    let email = value.trim_start_matches("mailto:").to_ascii_lowercase();
+                    if !intel.emails.contains(&email) {
+                        intel.emails.push(email);
+                    }
+                }
+                _ => {}
+            }
+        }
+        collect_entities(&entity["entities"], intel);
+    }
+}
@@ -0,0 +1,243 @@
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+/// JSON / microdata keys carrying the account creation time.
+const CREATED_KEYS: &[&str] = &[
+    "created_at",
+    "createdAt",
+    "dateCreated",
+    "created",
+    "member_since",
+    "memberSince",
+    "joined",
+];
+
+/// JSON / microdata keys carrying the last activity time.
+const ACTIVE_KEYS: &[&str] = &[
+    "last_active",
+    "lastActive",
+    "last_seen",
+    "lastSeen",
+    "last_online",
+    "lastOnline",
+    "updated_at",
+    "updatedAt",
+    "dateModified",
+];
+
+/// Public profile fields scraped from a hit's page.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProfileMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bio: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// Organisation behind the record, such as a domain's registrar; not a
+    /// person's name, so it is never scored for similarity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_active: Option<DateTime<Utc>>,
+}
+
+impl ProfileMetadata {
+    pub fn is_empty(&self) -> bool {
+        self.display_name.is_none()
+            && self.bio.is_none()
+            && self.location.is_none()
+            && self.organization.is_none()
+            && self.created_at.is_none()
+            && self.last_active.is_none()
+    }
+}
+
+/// Pull display name, bio, location and account timestamps out of a profile page.
+///
+/// Uses OpenGraph / `itemprop` meta tags and embedded JSON, falling back to
+/// `<title>` for the name. A trailing `· GitHub`-style segment naming `site`
+/// (the provider) or the page's `og:site_name` is dropped from the name.
+/// Returns `None` when nothing usable is present.
+pub fn extract_metadata(body: &str, site: &str) -> Option<ProfileMetadata> {
+    let metas = meta_tags(body);
+    let meta = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|k| metas.iter().find(|(name, _)| name == k))
+            .map(|(_, content)| content.clone())
+            .filter(|c| !c.is_empty())
+    };
+
+    let site_name = meta(&["og:site_name"]);
+    let sites = [Some(site), site_name.as_deref()];
+    let display_name = meta(&["name", "og:title", "twitter:title"])
+        .or_else(|| title(body))
+        .map(|t| strip_site_suffix(&t, sites.iter().flatten().copied()))
+        .filter(|t| !t.is_empty());
+    let bio = meta(&["og:description", "description", "twitter:description"]);
+    let location = meta(&["homelocation", "location"]).or_else(|| json_scalar(body, "location"));
+    let timestamp = |keys: &[&str]| {
+        keys.iter().find_map(|key| {
+            meta(&[key.to_ascii_lowercase().as_str()])
+                .or_else(|| json_scalar(body, key))
+                .and_then(|v| parse_timestamp(&v))
+        })
+    };
+
+    let metadata = ProfileMetadata {
+        display_name,
+        bio,
+        location,
+        organization: None,
+        created_at: timestamp(CREATED_KEYS),
+        last_active: timestamp(ACTIVE_KEYS),
+    };
+    (!metadata.is_empty()).then_some(metadata)
+}
+
+/// `(property|name|itemprop, content)` pairs of every `<meta>` tag, keys lowercased.
+fn meta_tags(body: &str) -> Vec<(String, String)> {
+    let lower = body.to_ascii_lowercase();
+    let mut out = Vec::new();
+    let mut from = 0;
+    while let Some(pos) = lower[from..].find("<meta") {
+        let start = from + pos;
+        let end = lower[start..].find('>').map_or(lower.len(), |e| start + e);
+        from = end;
+        let tag = &body[start..end];
+        let key = ["property", "name", "itemprop"]
+            .iter()
+            .find_map(|attr| attribute(tag, attr));
+        if let (Some(key), Some(content)) = (key, attribute(tag, "content")) {
+            out.push((key.to_ascii_lowercase(), decode_entities(content.trim())));
+        }
+    }
+    out
+}
+
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let lower = tag.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(pos) = lower[from..].find(name) {
+        let start = from + pos;
+        from = start + name.len();
+        let preceded = lower[..start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_ascii_whitespace());
+        let rest = lower[from..].trim_start();
+        if !preceded || !rest.starts_with('=') {
+            continue;
+        }
+        let value_start = tag.len() - rest.len() + 1;
+        let value = tag[value_start..].trim_start();
+        let quote = value.chars().next()?;
+        if quote == '"' || quote == '\'' {
+            let inner = &value[1..];
+            return inner.find(quote).map(|end| &inner[..end]);
+        }
+        let end = value
+            .find(|c: char| c.is_ascii_whitespace() || c == '/')
+            .unwrap_or(value.len());
+        return Some(&value[..end]);
+    }
+    None
+}
+
+fn title(body: &str) -> Option<String> {
+    let lower = body.to_ascii_lowercase();
+    let open = lower.find("<title")?;
+    let start = open + lower[open..].find('>')? + 1;
+    let end = start + lower[start..].find("</title")?;
+    Some(decode_entities(body[start..end].trim()))
+}
+
+/// First string or number value of `"key": ...` in embedded JSON.
+fn json_scalar(body: &str, key: &str) -> Option<String> {
+    let needle = format!("\"{}\"", key);
+    let mut from = 0;
+    while let Some(pos) = body[from..].find(&needle) {
+        from += pos + needle.len();
+        let rest = body[from..].trim_start();
+        let Some(rest) = rest.strip_prefix(':') else {
+            continue;
+        };
+        let rest = rest.trim_start();
+        let value = if let Some(quoted) = rest.strip_prefix('"') {
+            &quoted[..quoted.find('"')?]
+        } else {
+            let end = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            &rest[..end]
+        };
+        let value = value.trim();
+        if !value.is_empty() {
+            return Some(decode_entities(value));
+        }
+    }
+    None
+}
+
+/// RFC 3339, `YYYY-MM-DD` or a unix timestamp in seconds / milliseconds.
+pub fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Some(date.and_hms_opt(0, 0, 0)?.and_utc());
+    }
+    let epoch: i64 = value.parse().ok()?;
+    // Anything past ~2286 in seconds is taken as milliseconds.
+    if epoch > 9_999_999_999 {
+        Utc.timestamp_millis_opt(epoch).single()
+    } else {
+        Utc.timestamp_opt(epoch, 0).single()
+    }
+}
+
+/// `"Jane Doe (@jd) · GitHub"` -> `"Jane Doe (@jd)"`: the last segment is
+/// dropped only when it names one of `sites`, so `"Dancing on Ice"` stays.
+fn strip_site_suffix<'a>(title: &str, sites: impl Iterator<Item = &'a str> + Clone) -> String {
+    let title = title.trim();
+    let last = [" · ", " | ", " - ", " — ", " on "]
+        .iter()
+        .filter_map(|sep| title.rsplit_once(sep))
+        .max_by_key(|(head, _)| head.len());
+    match last {
+        Some((head, tail)) if sites.clone().any(|site| names_site(tail, site)) => {
+            head.trim().to_string()
+        }
+        _ => title.to_string(),
+    }
+}
+
+/// Whether a title segment like `"GitHub"` or `"Reddit.com"` names `site`.
+fn names_site(segment: &str, site: &str) -> bool {
+    let squash = |s: &str| -> String {
+        s.chars()
+            .filter(|c| c.is_alphanumeric() || *c == '.')
+            .collect::<String>()
+            .to_lowercase()
+    };
+    let (segment, site) = (squash(segment), squash(site));
+    !site.is_empty()
+        && segment
+            .strip_prefix(&site)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+fn decode_entities(value: &str) -> String {
+    value
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
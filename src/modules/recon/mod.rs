@@ -1,3 +1,5 @@
 //! Reconnaissance module.
 
+pub mod domain;
+pub mod metadata;
 pub mod username;
@@ -2,17 +2,35 @@ use reqwest::{Client, StatusCode};
 
 use crate::{
     config::ProviderConfig,
-    core::{engine::ProviderOutcome, error::FalconError},
+    core::{engine::ProviderOutcome, error::FalconError, evidence::Capture},
 };
 
+/// Upper bound on how much of a profile page is read for cross-linking.
+const MAX_BODY_BYTES: usize = 512 * 1024;
+
+/// Outcome of a provider check plus the (capped) page body for hits.
+#[derive(Debug, Clone)]
+pub struct ProviderCheck {
+    pub outcome: ProviderOutcome,
+    pub body: Option<String>,
+    /// The raw response behind a hit, for the evidence vault.
+    pub capture: Option<Capture>,
+}
+
+/// Profile URL for `username` on `provider`.
+pub fn profile_url(provider: &ProviderConfig, username: &str) -> String {
+    provider.base_url.replace("{username}", username)
+}
+
 /// Check a single provider for username presence.
 pub async fn check_provider(
     client: &Client,
     provider: &ProviderConfig,
     username: &str,
-) -> Result<ProviderOutcome, FalconError> {
-    let url = provider.base_url.replace("{username}", username);
-    let status = client.get(url).send().await?.status();
+) -> Result<ProviderCheck, FalconError> {
+    let url = profile_url(provider, username);
+    let mut response = client.get(&url).send().await?;
+    let status = response.status();
     if matches!(
         status,
         StatusCode::OK
@@ -22,13 +40,55 @@ pub async fn check_provider(
             | StatusCode::TEMPORARY_REDIRECT
             | StatusCode::PERMANENT_REDIRECT
     ) {
-        return Ok(ProviderOutcome::Hit);
-    }
-    if status == StatusCode::TOO_MANY_REQUESTS {
-        return Ok(ProviderOutcome::RateLimited);
+        let final_url = response.url().to_string();
+        let headers = response
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.to_string(),
+                    String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                )
+            })
+            .collect();
+        let mut body = Vec::new();
+        let mut truncated = false;
+        while let Some(chunk) = response.chunk().await? {
+            let room = MAX_BODY_BYTES - body.len();
+            truncated = chunk.len() > room;
+            body.extend_from_slice(&chunk[..chunk.len().min(room)]);
+            if body.len() >= MAX_BODY_BYTES {
+                // Exactly full: the page was longer if anything is left.
+                truncated = truncated || response.chunk().await?.is_some();
+                break;
+            }
+        }
+        return Ok(ProviderCheck {
+            outcome: ProviderOutcome::Hit,
+            body: Some(String::from_utf8_lossy(&body).into_owned()),
+            capture: Some(Capture {
+                url,
+                final_url,
+                status: status.as_u16(),
+                headers,
+                body,
+                truncated,
+            }),
+        });
     }
-    if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
-        return Ok(ProviderOutcome::Restricted);
+    if status.is_server_error() {
+        return Err(FalconError::Http(status.to_string()));
     }
-    Ok(ProviderOutcome::Miss)
+    let outcome = if status == StatusCode::TOO_MANY_REQUESTS {
+        ProviderOutcome::RateLimited
+    } else if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+        ProviderOutcome::Restricted
+    } else {
+        ProviderOutcome::Miss
+    };
+    Ok(ProviderCheck {
+        outcome,
+        body: None,
+        capture: None,
+    })
 }
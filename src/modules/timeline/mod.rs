@@ -0,0 +1,158 @@
+//! Per-target account timeline from provider timestamps.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+use crate::core::engine::ReconResult;
+
+/// Age thresholds for classifying account activity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ActivityWindow {
+    /// Last activity within this many days counts as recent.
+    pub recent_days: i64,
+    /// No activity for this many days counts as dormant.
+    pub dormant_days: i64,
+}
+
+impl Default for ActivityWindow {
+    fn default() -> Self {
+        Self {
+            recent_days: 30,
+            dormant_days: 365,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    Created,
+    LastActive,
+}
+
+impl EventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventKind::Created => "created",
+            EventKind::LastActive => "last_active",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Activity {
+    Recent,
+    Active,
+    Dormant,
+    Unknown,
+}
+
+impl Activity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Activity::Recent => "recent",
+            Activity::Active => "active",
+            Activity::Dormant => "dormant",
+            Activity::Unknown => "unknown",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TimelineEvent {
+    pub at: DateTime<Utc>,
+    pub provider: String,
+    pub kind: EventKind,
+    pub url: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AccountActivity {
+    pub provider: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_active: Option<DateTime<Utc>>,
+    /// 1-based position in account creation order, when known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creation_rank: Option<usize>,
+    pub activity: Activity,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Timeline {
+    pub target: String,
+    /// Earliest known account creation: when the persona was established.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub established: Option<DateTime<Utc>>,
+    pub events: Vec<TimelineEvent>,
+    pub accounts: Vec<AccountActivity>,
+}
+
+impl Timeline {
+    pub fn build(result: &ReconResult, now: DateTime<Utc>, window: ActivityWindow) -> Self {
+        let mut events = Vec::new();
+        let mut accounts = Vec::new();
+        for profile in &result.profiles {
+            let meta = profile.metadata.clone().unwrap_or_default();
+            for (at, kind) in [
+                (meta.created_at, EventKind::Created),
+                (meta.last_active, EventKind::LastActive),
+            ] {
+                if let Some(at) = at {
+                    events.push(TimelineEvent {
+                        at,
+                        provider: profile.provider.clone(),
+                        kind,
+                        url: profile.url.clone(),
+                    });
+                }
+            }
+            let activity = match meta.last_active {
+                Some(at) if now - at <= Duration::days(window.recent_days) => Activity::Recent,
+                Some(at) if now - at >= Duration::days(window.dormant_days) => Activity::Dormant,
+                Some(_) => Activity::Active,
+                None => Activity::Unknown,
+            };
+            accounts.push(AccountActivity {
+                provider: profile.provider.clone(),
+                created_at: meta.created_at,
+                last_active: meta.last_active,
+                creation_rank: None,
+                activity,
+            });
+        }
+        events.sort_by(|a, b| a.at.cmp(&b.at).then_with(|| a.provider.cmp(&b.provider)));
+
+        let mut created: Vec<(DateTime<Utc>, usize)> = accounts
+            .iter()
+            .enumerate()
+            .filter_map(|(i, a)| a.created_at.map(|at| (at, i)))
+            .collect();
+        created.sort();
+        for (rank, (_, i)) in created.iter().enumerate() {
+            accounts[*i].creation_rank = Some(rank + 1);
+        }
+        accounts.sort_by_key(|a| (a.creation_rank.is_none(), a.creation_rank));
+
+        Self {
+            target: result.target.clone(),
+            established: created.first().map(|(at, _)| *at),
+            events,
+            accounts,
+        }
+    }
+
+    pub fn dormant(&self) -> impl Iterator<Item = &AccountActivity> {
+        self.accounts
+            .iter()
+            .filter(|a| a.activity == Activity::Dormant)
+    }
+
+    pub fn recent(&self) -> impl Iterator<Item = &AccountActivity> {
+        self.accounts
+            .iter()
+            .filter(|a| a.activity == Activity::Recent)
+    }
+}
@@ -0,0 +1,355 @@
+//! Scheduled rescans that alert when accounts appear or vanish.
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::{
+        crypto::{self, KeySource},
+        engine::{Engine, ReconResult, TargetKind},
+        error::FalconError,
+    },
+    export::siem::SiemSinks,
+    modules::diff::{diff, provider_states, ChangeKind, ProviderState},
+};
+
+/// When targets are rescanned.
+#[derive(Clone, Debug)]
+pub enum Schedule {
+    Every(Duration),
+    Cron(Box<cron::Schedule>),
+}
+
+impl Schedule {
+    /// Parse a cron expression; five-field (minute-first) expressions get a
+    /// leading seconds field of `0`.
+    pub fn cron(expr: &str) -> Result<Self, FalconError> {
+        let expr = expr.trim();
+        let full = if expr.split_whitespace().count() == 5 {
+            format!("0 {}", expr)
+        } else {
+            expr.to_string()
+        };
+        cron::Schedule::from_str(&full)
+            .map(|s| Schedule::Cron(Box::new(s)))
+            .map_err(|e| FalconError::Config(format!("invalid cron expression `{}`: {}", expr, e)))
+    }
+
+    /// First run time after a run at `last`.
+    pub fn next_after(&self, last: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Schedule::Every(interval) => chrono::Duration::from_std(*interval)
+                .ok()
+                .and_then(|step| last.checked_add_signed(step))
+                .unwrap_or(DateTime::<Utc>::MAX_UTC),
+            Schedule::Cron(schedule) => schedule
+                .after(&last)
+                .next()
+                .unwrap_or(DateTime::<Utc>::MAX_UTC),
+        }
+    }
+}
+
+/// An account that appeared or vanished between two scans.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Alert {
+    pub at: DateTime<Utc>,
+    pub target: String,
+    pub kind: TargetKind,
+    pub provider: String,
+    /// `appeared` or `disappeared`.
+    pub change: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// Alerts for accounts that appeared or disappeared from `before` to `after`.
+pub fn alerts_for(
+    before: &ReconResult,
+    after: &ReconResult,
+    at: DateTime<Utc>,
+) -> Result<Vec<Alert>, FalconError> {
+    let changes = diff(before, after, None, Some(at))?;
+    Ok(changes
+        .changes
+        .iter()
+        .filter(|c| c.change != ChangeKind::Changed)
+        .map(|c| Alert {
+            at,
+            target: after.target.clone(),
+            kind: after.kind,
+            provider: c.provider.clone(),
+            change: c.change.as_str().to_string(),
+            url: after
+                .profiles
+                .iter()
+                .chain(&before.profiles)
+                .find(|p| p.provider == c.provider)
+                .map(|p| p.url.clone()),
+        })
+        .collect())
+}
+
+/// What the next scan of a target is compared with: `result`, except that a
+/// provider whose check failed or stayed rate limited keeps its state from
+/// `previous`, so a vanished account still alerts once the provider answers.
+pub fn baseline(previous: &ReconResult, result: &ReconResult) -> ReconResult {
+    let mut out = result.clone();
+    let old = provider_states(previous);
+    let pending: Vec<String> = provider_states(result)
+        .into_iter()
+        .filter(|(_, state)| matches!(state, ProviderState::Failed | ProviderState::RateLimited))
+        .map(|(provider, _)| provider)
+        .collect();
+    let is_pending = |provider: &str| pending.iter().any(|p| p == provider);
+    out.failed
+        .retain(|f| !is_pending(f.split_once(':').map_or(f.as_str(), |(p, _)| p)));
+    out.rate_limited.retain(|p| !is_pending(p));
+    for provider in &pending {
+        match old.get(provider) {
+            Some(ProviderState::Hit) => {
+                out.platforms.push(provider.clone());
+                out.hits += 1;
+                out.profiles.extend(
+                    previous
+                        .profiles
+                        .iter()
+                        .filter(|p| &p.provider == provider)
+                        .cloned(),
+                );
+            }
+            Some(ProviderState::Restricted) => out.restricted.push(provider.clone()),
+            // Never answered definitively: still unknown.
+            Some(ProviderState::Failed | ProviderState::RateLimited) => {
+                out.failed.extend(
+                    result
+                        .failed
+                        .iter()
+                        .filter(|f| f.split_once(':').map_or(f.as_str(), |(p, _)| p) == provider)
+                        .cloned(),
+                );
+                if result.rate_limited.contains(provider) {
+                    out.rate_limited.push(provider.clone());
+                }
+            }
+            // Last seen as a miss.
+            None => {}
+        }
+    }
+    out
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct WatchedTarget {
+    pub kind: TargetKind,
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run: Option<DateTime<Utc>>,
+    /// Baseline for the next scan; see [`baseline`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_result: Option<ReconResult>,
+}
+
+/// Last run and result per target; persisted so schedules survive restarts.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct WatchState {
+    pub targets: BTreeMap<String, WatchedTarget>,
+}
+
+impl WatchState {
+    pub fn load(path: &Path, key: Option<&KeySource>) -> Result<Self, FalconError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let data = crypto::read_file(path, key)?;
+        serde_json::from_slice(&data)
+            .map_err(|e| FalconError::Config(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Save via temp file + rename so a crash never leaves half a state file.
+    pub fn save(&self, path: &Path, key: Option<&KeySource>) -> Result<(), FalconError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_vec_pretty(self).map_err(|_| FalconError::Unknown)?;
+        let tmp = path.with_extension("tmp");
+        crypto::write_file(&tmp, &json, key)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    fn entry(&mut self, kind: TargetKind, id: &str) -> &mut WatchedTarget {
+        let key = match kind {
+            TargetKind::Username => id.to_string(),
+            TargetKind::Domain => format!("domain:{}", id),
+        };
+        self.targets.entry(key).or_insert_with(|| WatchedTarget {
+            kind,
+            id: id.to_string(),
+            ..Default::default()
+        })
+    }
+}
+
+/// Where alerts go.
+#[derive(Clone, Debug, Default)]
+pub struct AlertSinks {
+    /// Print NDJSON to stdout.
+    pub stdout: bool,
+    /// Append NDJSON to this file. Always plaintext, so it can be tailed
+    /// and shipped; the key only covers the watch state.
+    pub log: Option<PathBuf>,
+    /// POST each alert as JSON to this URL.
+    pub webhook: Option<String>,
+}
+
+impl AlertSinks {
+    /// Deliver `alerts`; webhook failures are logged, not fatal.
+    pub async fn send(
+        &self,
+        client: &reqwest::Client,
+        alerts: &[Alert],
+    ) -> Result<(), FalconError> {
+        if alerts.is_empty() {
+            return Ok(());
+        }
+        let mut lines = String::new();
+        for alert in alerts {
+            lines.push_str(&serde_json::to_string(alert).map_err(|_| FalconError::Unknown)?);
+            lines.push('\n');
+        }
+        if self.stdout {
+            print!("{}", lines);
+        }
+        if let Some(path) = &self.log {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            file.write_all(lines.as_bytes())?;
+        }
+        if let Some(url) = &self.webhook {
+            for alert in alerts {
+                match client.post(url).json(alert).send().await {
+                    Ok(response) if response.status().is_success() => {}
+                    Ok(response) => {
+                        tracing::warn!("webhook {} answered {}", url, response.status())
+                    }
+                    Err(e) => tracing::warn!("webhook {} failed: {}", url, e),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rescans a fixed set of targets on a schedule.
+pub struct Watcher {
+    pub schedule: Schedule,
+    pub targets: Vec<(TargetKind, String)>,
+    pub sinks: AlertSinks,
+    state_path: PathBuf,
+    key: Option<KeySource>,
+    state: WatchState,
+    client: reqwest::Client,
+    siem: Option<SiemSinks>,
+}
+
+impl Watcher {
+    /// Load (or start) the schedule state at `state_path`.
+    pub fn new(
+        schedule: Schedule,
+        targets: Vec<(TargetKind, String)>,
+        sinks: AlertSinks,
+        state_path: PathBuf,
+        key: Option<KeySource>,
+    ) -> Result<Self, FalconError> {
+        let state = WatchState::load(&state_path, key.as_ref())?;
+        Ok(Self {
+            schedule,
+            targets,
+            sinks,
+            state_path,
+            key,
+            state,
+            client: reqwest::Client::new(),
+            siem: None,
+        })
+    }
+
+    /// Also post every scan result to `siem`.
+    pub fn with_siem(mut self, siem: Option<SiemSinks>) -> Self {
+        self.siem = siem;
+        self
+    }
+
+    pub fn state(&self) -> &WatchState {
+        &self.state
+    }
+
+    /// When `kind`/`id` is next due; never-run targets are due immediately.
+    pub fn due_at(&self, kind: TargetKind, id: &str) -> Option<DateTime<Utc>> {
+        let last = self
+            .state
+            .targets
+            .values()
+            .find(|t| t.kind == kind && t.id == id)
+            .and_then(|t| t.last_run)?;
+        Some(self.schedule.next_after(last))
+    }
+
+    /// Earliest time any target is due.
+    pub fn next_due(&self) -> Option<DateTime<Utc>> {
+        self.targets
+            .iter()
+            .map(|(kind, id)| self.due_at(*kind, id).unwrap_or(DateTime::<Utc>::MIN_UTC))
+            .min()
+    }
+
+    /// Scan every target due at `now`, send alerts and save state.
+    pub async fn tick(
+        &mut self,
+        engine: &Engine,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Alert>, FalconError> {
+        let mut alerts = Vec::new();
+        let mut scanned = Vec::new();
+        let targets = self.targets.clone();
+        for (kind, id) in targets {
+            if self.due_at(kind, &id).is_some_and(|due| due > now) {
+                continue;
+            }
+            let result = match engine.scan_target(kind, &id, false).await {
+                Ok(result) => result,
+                Err(e) => {
+                    tracing::warn!("watch scan of {} failed: {}", id, e);
+                    continue;
+                }
+            };
+            let entry = self.state.entry(kind, &id);
+            if let Some(previous) = &entry.last_result {
+                alerts.extend(alerts_for(previous, &result, now)?);
+            }
+            entry.last_run = Some(now);
+            if self.siem.is_some() {
+                scanned.push(result.clone());
+            }
+            entry.last_result = Some(match &entry.last_result {
+                Some(previous) => baseline(previous, &result),
+                None => result,
+            });
+        }
+        self.state.save(&self.state_path, self.key.as_ref())?;
+        if let Some(siem) = &self.siem {
+            siem.send(&scanned, now).await;
+        }
+        self.sinks.send(&self.client, &alerts).await?;
+        Ok(alerts)
+    }
+}
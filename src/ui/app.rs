@@ -1,12 +1,27 @@
-use std::fmt;
+use std::{fmt, path::Path};
 
-use chrono::Local;
+use chrono::{DateTime, Local, Utc};
 
-use crate::core::engine::ReconResult;
+use crate::{
+    core::{
+        case::Case,
+        crypto::KeySource,
+        engine::{ReconResult, TargetKind},
+        error::FalconError,
+        logger::LogFeed,
+    },
+    export::envelope,
+    modules::crosslink::{
+        pivot::{PivotPolicy, PivotQueue, Provenance},
+        similarity::AccountSimilarity,
+        IdentifierKind,
+    },
+};
 
 #[derive(Clone)]
 pub struct Target {
     pub id: String,
+    pub kind: TargetKind,
     pub label: Option<String>,
     pub status: Status,
     pub hits: usize,
@@ -15,21 +30,101 @@ pub struct Target {
     pub failed: Vec<String>,
     pub restricted: Vec<String>,
     pub rate_limited: Vec<String>,
+    pub provenance: Option<Provenance>,
+    pub similarity: Vec<AccountSimilarity>,
+    /// Full result of the last completed scan.
+    pub result: Option<ReconResult>,
+    /// Result of the scan before that, for the diff view.
+    pub previous: Option<ReconResult>,
+    /// When `result` was scanned (the original time for loaded results).
+    pub scanned_at: Option<DateTime<Utc>>,
+    /// When `previous` was scanned.
+    pub previous_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Clone, PartialEq)]
+impl Target {
+    pub fn new(id: String, label: Option<String>) -> Self {
+        Self {
+            id,
+            kind: TargetKind::Username,
+            label,
+            status: Status::Empty,
+            hits: 0,
+            emails: vec![],
+            platforms: vec![],
+            failed: vec![],
+            restricted: vec![],
+            rate_limited: vec![],
+            provenance: None,
+            similarity: vec![],
+            result: None,
+            previous: None,
+            scanned_at: None,
+            previous_at: None,
+        }
+    }
+
+    /// Show `result`, scanned at `at`, as this target's latest scan.
+    pub fn apply(&mut self, result: &ReconResult, at: DateTime<Utc>) {
+        self.status = Status::Found;
+        self.previous = self.result.replace(result.clone());
+        self.previous_at = self.scanned_at.replace(at);
+        self.hits = result.hits;
+        self.emails = result
+            .profiles
+            .iter()
+            .flat_map(|p| &p.linked)
+            .filter(|l| l.kind == IdentifierKind::Email)
+            .map(|l| l.value.clone())
+            .collect();
+        self.emails.sort();
+        self.emails.dedup();
+        self.platforms = result.platforms.clone();
+        self.failed = result.failed.clone();
+        self.restricted = result.restricted.clone();
+        self.rate_limited = result.rate_limited.clone();
+        self.similarity = result.similarity.clone();
+    }
+}
+
+/// Tab shown in the middle panel.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum View {
+    Intel,
+    Timeline,
+    Diff,
+}
+
+impl View {
+    pub const ALL: [View; 3] = [View::Intel, View::Timeline, View::Diff];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            View::Intel => "INTEL FEED",
+            View::Timeline => "TIMELINE",
+            View::Diff => "DIFF",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub enum Status {
+    Queued,
     Scanning,
     Found,
     Empty,
+    /// Showing a result read back from a saved file.
+    Loaded,
 }
 
 impl fmt::Display for Status {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            Status::Queued => write!(f, "⏳ QUEUED"),
             Status::Scanning => write!(f, "🦅 SCANNING"),
             Status::Found => write!(f, "✅ HIT"),
             Status::Empty => write!(f, "❌ EMPTY"),
+            Status::Loaded => write!(f, "📂 LOADED"),
         }
     }
 }
@@ -40,6 +135,13 @@ pub struct App {
     pub input: String,
     pub logs: Vec<String>,
     pub scanning: bool,
+    pub view: View,
+    /// Set when discovered identifiers are queued as targets automatically.
+    pub pivot: Option<PivotQueue>,
+    /// Case workspace that targets and scan results are recorded to.
+    pub case: Option<Case>,
+    /// Tracing output routed to the log panel.
+    pub log_feed: Option<LogFeed>,
 }
 
 impl App {
@@ -55,45 +157,169 @@ impl App {
                 "[SYSTEM] ENTER TARGET IDENTIFIER".to_string(),
             ],
             scanning: false,
+            view: View::Intel,
+            pivot: None,
+            case: None,
+            log_feed: None,
+        }
+    }
+
+    /// Load a case's targets (with their latest results) and record to it from now on.
+    pub fn attach_case(&mut self, mut case: Case) {
+        let errors: Vec<String> = self
+            .targets
+            .iter()
+            .filter_map(|t| case.add_target(t.kind, &t.id, t.label.clone()).err())
+            .map(|e| e.to_string())
+            .collect();
+        for e in errors {
+            self.log(format!("[WARN] case: {}", e));
+        }
+        for saved in case.targets() {
+            if self
+                .targets
+                .iter()
+                .any(|t| t.kind == saved.kind && t.id == saved.id)
+            {
+                continue;
+            }
+            let mut target = Target::new(saved.id.clone(), saved.label.clone());
+            target.kind = saved.kind;
+            // Replay the last two scans so the diff view has a baseline.
+            for scan in saved.scans.iter().rev().take(2).rev() {
+                target.apply(&scan.result, scan.at);
+            }
+            if let Some(last) = saved.last_scan() {
+                target.provenance = last.result.provenance.clone();
+            }
+            self.targets.push(target);
+        }
+        self.log(format!(
+            "[CASE] {} opened: {} targets",
+            case.name(),
+            case.targets().len()
+        ));
+        self.case = Some(case);
+    }
+
+    /// Add the results saved in `path` (headless JSON, JSON lines or an
+    /// envelope) as loaded targets; rescanning one diffs against the loaded
+    /// result. Returns how many results were loaded.
+    pub fn load_saved(
+        &mut self,
+        path: &Path,
+        key: Option<&KeySource>,
+    ) -> Result<usize, FalconError> {
+        let saved = envelope::read_saved(path, key)?;
+        let count = saved.results.len();
+        for result in saved.results {
+            if let Some(queue) = self.pivot.as_mut() {
+                queue.mark_seen(&result.target);
+            }
+            let idx = match self
+                .targets
+                .iter()
+                .position(|t| t.kind == result.kind && t.id == result.target)
+            {
+                Some(idx) => idx,
+                None => {
+                    let mut target = Target::new(result.target.clone(), None);
+                    target.kind = result.kind;
+                    self.targets.push(target);
+                    self.targets.len() - 1
+                }
+            };
+            let target = &mut self.targets[idx];
+            target.apply(&result, saved.scanned_at);
+            target.provenance = result.provenance.clone();
+            target.status = Status::Loaded;
         }
+        self.log(format!(
+            "[LOAD] {}: {} results scanned {}",
+            path.display(),
+            count,
+            saved
+                .scanned_at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+        ));
+        Ok(count)
+    }
+
+    pub fn enable_pivot(&mut self, policy: PivotPolicy) {
+        let mut queue = PivotQueue::new(policy);
+        for target in &self.targets {
+            queue.mark_seen(&target.id);
+        }
+        self.pivot = Some(queue);
+        self.log(format!(
+            "[SYSTEM] PIVOT MODE: depth {} / max {} targets",
+            policy.max_depth, policy.max_targets
+        ));
     }
 
-    pub fn add_target(&mut self, id: String) {
-        self.add_target_with_label(id, None);
+    /// Adds a target from user input; `domain:example.com` adds a domain.
+    pub fn add_target(&mut self, input: String) {
+        let (kind, id) = TargetKind::parse_input(&input);
+        self.add_typed_target(kind, id, None);
     }
 
     pub fn add_target_with_label(&mut self, id: String, label: Option<String>) {
-        self.targets.push(Target {
-            id: id.clone(),
-            label,
-            status: Status::Empty,
-            hits: 0,
-            emails: vec![],
-            platforms: vec![],
-            failed: vec![],
-            restricted: vec![],
-            rate_limited: vec![],
-        });
+        self.add_typed_target(TargetKind::Username, id, label);
+    }
+
+    pub fn add_typed_target(&mut self, kind: TargetKind, id: String, label: Option<String>) {
+        if let Some(queue) = self.pivot.as_mut() {
+            queue.mark_seen(&id);
+        }
+        let recorded = self
+            .case
+            .as_mut()
+            .map(|case| case.add_target(kind, &id, label.clone()));
+        if let Some(Err(e)) = recorded {
+            self.log(format!("[WARN] case: {}", e));
+        }
+        let mut target = Target::new(id.clone(), label);
+        target.kind = kind;
+        self.targets.push(target);
         self.log(format!("[+] Target added: {}", id));
     }
 
-    /// Marks the current target as scanning and returns (index, id).
-    pub fn start_scan(&mut self) -> Option<(usize, String)> {
+    /// Marks the current target as scanning and returns (index, kind, id).
+    pub fn start_scan(&mut self) -> Option<(usize, TargetKind, String)> {
         if self.targets.is_empty() {
             return None;
         }
         let idx = self.current_target.min(self.targets.len() - 1);
-        let target_id = {
+        self.start_scan_at(idx)
+    }
+
+    /// Starts the next queued pivot target, if any.
+    pub fn start_next_pivot(&mut self) -> Option<(usize, TargetKind, String)> {
+        let next = self.pivot.as_mut()?.pop()?;
+        let idx = self.targets.iter().position(|t| t.id == next.username)?;
+        self.start_scan_at(idx)
+    }
+
+    fn start_scan_at(&mut self, idx: usize) -> Option<(usize, TargetKind, String)> {
+        let (kind, target_id) = {
             let target = &mut self.targets[idx];
             target.status = Status::Scanning;
-            target.id.clone()
+            (target.kind, target.id.clone())
         };
         self.scanning = true;
-        self.log(format!("🦅 SCANNING {} across 348 platforms...", target_id));
-        Some((idx, target_id))
+        match kind {
+            TargetKind::Username => {
+                self.log(format!("🦅 SCANNING {} across 348 platforms...", target_id))
+            }
+            TargetKind::Domain => {
+                self.log(format!("🦅 SCANNING {} via RDAP + CT logs...", target_id))
+            }
+        }
+        Some((idx, kind, target_id))
     }
 
-    pub fn complete_scan(&mut self, idx: usize, outcome: ReconResult) {
+    pub fn complete_scan(&mut self, idx: usize, mut outcome: ReconResult) {
         #[derive(Default)]
         struct LogInfo {
             id: String,
@@ -107,14 +333,13 @@ impl App {
         }
 
         let mut log_items: Option<LogInfo> = None;
+        let mut derived = Vec::new();
         if let Some(target) = self.targets.get_mut(idx) {
-            target.status = Status::Found;
-            target.hits = outcome.hits;
-            target.emails.clear();
-            target.platforms = outcome.platforms;
-            target.failed = outcome.failed;
-            target.restricted = outcome.restricted;
-            target.rate_limited = outcome.rate_limited;
+            outcome.provenance = target.provenance.clone();
+            if let Some(queue) = self.pivot.as_mut() {
+                derived = queue.offer(&outcome);
+            }
+            target.apply(&outcome, Utc::now());
             log_items = Some(LogInfo {
                 id: target.id.clone(),
                 hits: target.hits,
@@ -140,6 +365,23 @@ impl App {
             if info.has_fail {
                 self.log(format!("[WARN] Failed providers: {}", info.failed));
             }
+            let recorded = self.case.as_mut().map(|case| case.record_scan(&outcome));
+            if let Some(Err(e)) = recorded {
+                self.log(format!("[WARN] case: {}", e));
+            }
+        }
+        for pivot in derived {
+            let Some(provenance) = pivot.provenance else {
+                continue;
+            };
+            self.log(format!(
+                "[PIVOT] {} <- {} via {}",
+                pivot.username, provenance.parent, provenance.provider
+            ));
+            let mut target = Target::new(pivot.username, None);
+            target.status = Status::Queued;
+            target.provenance = Some(provenance);
+            self.targets.push(target);
         }
         self.scanning = false;
     }
@@ -155,10 +397,37 @@ impl App {
         self.scanning = false;
     }
 
+    pub fn next_view(&mut self) {
+        let idx = View::ALL.iter().position(|v| *v == self.view).unwrap_or(0);
+        self.view = View::ALL[(idx + 1) % View::ALL.len()];
+    }
+
+    pub fn prev_view(&mut self) {
+        let idx = View::ALL.iter().position(|v| *v == self.view).unwrap_or(0);
+        self.view = View::ALL[(idx + View::ALL.len() - 1) % View::ALL.len()];
+    }
+
     pub fn next_target(&mut self) {
         self.current_target = (self.current_target + 1) % self.targets.len().max(1);
     }
 
+    /// Show tracing output in the log panel from now on.
+    pub fn attach_log_feed(&mut self, feed: LogFeed) {
+        self.log_feed = Some(feed);
+    }
+
+    /// Move lines logged through tracing into the log panel.
+    pub fn pull_logs(&mut self) {
+        let lines = self
+            .log_feed
+            .as_ref()
+            .map(LogFeed::drain)
+            .unwrap_or_default();
+        for line in lines {
+            self.log(line);
+        }
+    }
+
     pub fn log(&mut self, msg: impl Into<String>) {
         self.logs.push(format!(
             "[{}] {}",
@@ -2,6 +2,7 @@ use std::io;
 use std::sync::Arc;
 use std::time::Duration;
 
+use chrono::{DateTime, Local, Utc};
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
     execute,
@@ -12,16 +13,28 @@ use ratatui::{
     layout::{Constraint, Direction, Layout},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Gauge, List, ListItem, Paragraph},
+    widgets::{Block, Borders, Gauge, List, ListItem, Paragraph, Tabs},
     Terminal,
 };
 use tokio::task::JoinHandle;
 
 use crate::{
-    core::{engine::Engine, error::FalconError},
-    ui::app::{App, Status, Target},
+    core::{
+        crypto::KeySource,
+        engine::{Engine, ReconResult, TargetKind},
+        error::FalconError,
+    },
+    modules::{
+        crosslink::similarity::Verdict,
+        diff::{diff, ChangeKind},
+        timeline::{ActivityWindow, Timeline},
+    },
+    ui::app::{App, Status, Target, View},
 };
 
+/// Deepest pivot indent drawn in the target list.
+const MAX_PIVOT_INDENT: usize = 8;
+
 pub async fn run_tui(
     engine: Arc<Engine>,
     mut app: App,
@@ -34,12 +47,10 @@ pub async fn run_tui(
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut scan_task: Option<(
-        usize,
-        JoinHandle<Result<crate::core::engine::ReconResult, FalconError>>,
-    )> = None;
+    let mut scan_task: Option<(usize, JoinHandle<Result<ReconResult, FalconError>>)> = None;
 
     loop {
+        app.pull_logs();
         terminal.draw(|f| draw_ui(f, &app))?;
 
         if crossterm::event::poll(Duration::from_millis(250))? {
@@ -52,14 +63,18 @@ pub async fn run_tui(
                                 app.add_target("shadow".to_string());
                             }
                             if scan_task.is_none() {
-                                if let Some((idx, id)) = app.start_scan() {
-                                    let engine = engine.clone();
-                                    let handle = tokio::spawn(async move {
-                                        engine.scan_username(&id, use_cache).await
-                                    });
-                                    scan_task = Some((idx, handle));
+                                if let Some((idx, kind, id)) = app.start_scan() {
+                                    scan_task =
+                                        Some((idx, spawn_scan(&engine, kind, id, use_cache)));
                                 }
                             }
+                        } else if let Some(path) = app.input.trim().strip_prefix(":load ") {
+                            let path = std::path::PathBuf::from(path.trim());
+                            let key = KeySource::resolve(engine.config.key_file.as_deref());
+                            if let Err(e) = app.load_saved(&path, key.as_ref()) {
+                                app.log(format!("[WARN] load: {}", e));
+                            }
+                            app.input.clear();
                         } else {
                             app.add_target(app.input.clone());
                             app.input.clear();
@@ -70,6 +85,8 @@ pub async fn run_tui(
                         app.input.pop();
                     }
                     KeyCode::Tab => app.next_target(),
+                    KeyCode::Right => app.next_view(),
+                    KeyCode::Left => app.prev_view(),
                     _ => {}
                 }
             }
@@ -87,6 +104,13 @@ pub async fn run_tui(
                 scan_task = Some((idx, handle));
             }
         }
+
+        // Pivot targets scan back to back once the engine is idle
+        if scan_task.is_none() {
+            if let Some((idx, kind, id)) = app.start_next_pivot() {
+                scan_task = Some((idx, spawn_scan(&engine, kind, id, use_cache)));
+            }
+        }
     }
 
     disable_raw_mode()?;
@@ -99,6 +123,16 @@ pub async fn run_tui(
     Ok(())
 }
 
+fn spawn_scan(
+    engine: &Arc<Engine>,
+    kind: TargetKind,
+    id: String,
+    use_cache: bool,
+) -> JoinHandle<Result<ReconResult, FalconError>> {
+    let engine = engine.clone();
+    tokio::spawn(async move { engine.scan_target(kind, &id, use_cache).await })
+}
+
 fn draw_ui(f: &mut ratatui::Frame, app: &App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -139,13 +173,25 @@ fn draw_ui(f: &mut ratatui::Frame, app: &App) {
         .enumerate()
         .map(|(i, t)| {
             let status_color = match t.status {
+                Status::Queued => Color::DarkGray,
                 Status::Scanning => Color::Yellow,
                 Status::Found => Color::Green,
                 Status::Empty => Color::White,
+                Status::Loaded => Color::Magenta,
             };
-            ListItem::new(Line::from(vec![
+            let mut spans = vec![
                 Span::styled(format!("{:2}", i), Style::default().fg(Color::Gray)),
                 Span::raw(" | "),
+            ];
+            if let Some(p) = &t.provenance {
+                // Loaded files carry any depth; keep the indent sane.
+                let indent = p.depth.saturating_sub(1).min(MAX_PIVOT_INDENT);
+                spans.push(Span::styled(
+                    format!("{}↳ ", "  ".repeat(indent)),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            spans.extend([
                 Span::styled(&t.id, Style::default().fg(status_color)),
                 Span::raw(" ["),
                 Span::styled(
@@ -153,7 +199,20 @@ fn draw_ui(f: &mut ratatui::Frame, app: &App) {
                     Style::default().fg(Color::Cyan),
                 ),
                 Span::raw("]"),
-            ]))
+            ]);
+            if let Some(p) = &t.provenance {
+                spans.push(Span::styled(
+                    format!(" via {} <- {}", p.provider, p.parent),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            if let (Status::Loaded, Some(at)) = (&t.status, t.scanned_at) {
+                spans.push(Span::styled(
+                    format!(" loaded, scanned {}", local_time(at)),
+                    Style::default().fg(Color::Magenta),
+                ));
+            }
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
@@ -168,17 +227,7 @@ fn draw_ui(f: &mut ratatui::Frame, app: &App) {
     f.render_widget(targets, chunks[1]);
 
     // Intel feed
-    let placeholder = Target {
-        id: "No Target".to_string(),
-        label: None,
-        status: Status::Empty,
-        hits: 0,
-        emails: vec![],
-        platforms: vec![],
-        failed: vec![],
-        restricted: vec![],
-        rate_limited: vec![],
-    };
+    let placeholder = Target::new("No Target".to_string(), None);
     let current = app.targets.get(app.current_target).unwrap_or(&placeholder);
 
     let mut intel_lines: Vec<Line> = vec![
@@ -196,6 +245,10 @@ fn draw_ui(f: &mut ratatui::Frame, app: &App) {
                 Style::default().fg(Color::Gray),
             ),
         ]),
+        Line::from(vec![
+            Span::styled("Lineage: ", Style::default().fg(Color::White)),
+            Span::styled(lineage(app, current), Style::default().fg(Color::Gray)),
+        ]),
         Line::from(vec![
             Span::styled("Status: ", Style::default().fg(Color::White)),
             Span::styled(
@@ -265,12 +318,102 @@ fn draw_ui(f: &mut ratatui::Frame, app: &App) {
         ]));
     }
 
-    let intel = Paragraph::new(intel_lines).block(
-        Block::default()
-            .title(" 🛡️ INTEL FEED ")
-            .borders(Borders::ALL),
-    );
-    f.render_widget(intel, chunks[2]);
+    if let Some(result) = current.result.as_ref().filter(|r| !r.cached.is_empty()) {
+        intel_lines.push(Line::from(vec![
+            Span::styled("Cached: ", Style::default().fg(Color::White)),
+            Span::styled(
+                result.cached.join(", "),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
+
+    if let Some(domain) = current.result.as_ref().and_then(|r| r.domain.as_ref()) {
+        let date = |d: Option<DateTime<Utc>>| {
+            d.map_or("?".to_string(), |d| d.format("%Y-%m-%d").to_string())
+        };
+        intel_lines.push(Line::from(vec![
+            Span::styled("Registrar: ", Style::default().fg(Color::White)),
+            Span::styled(
+                domain
+                    .registrar
+                    .clone()
+                    .unwrap_or_else(|| "Unknown".to_string()),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::raw(format!(
+                "  registered {} / expires {}",
+                date(domain.registered),
+                date(domain.expires)
+            )),
+        ]));
+        if !domain.nameservers.is_empty() {
+            intel_lines.push(Line::from(vec![
+                Span::styled("Nameservers: ", Style::default().fg(Color::White)),
+                Span::raw(domain.nameservers.join(", ")),
+            ]));
+        }
+        intel_lines.push(Line::from(vec![
+            Span::styled("CT hostnames: ", Style::default().fg(Color::White)),
+            Span::styled(
+                domain.hostnames.len().to_string(),
+                Style::default().fg(Color::Green),
+            ),
+        ]));
+    }
+
+    for pair in &current.similarity {
+        let color = match pair.verdict() {
+            Verdict::LikelySame => Color::Green,
+            Verdict::Possible => Color::Yellow,
+            Verdict::Unlikely => Color::DarkGray,
+        };
+        intel_lines.push(Line::from(vec![
+            Span::styled("Persona: ", Style::default().fg(Color::White)),
+            Span::raw(format!("{} ~ {} ", pair.a, pair.b)),
+            Span::styled(
+                format!("{:.2} ({})", pair.score, pair.verdict().as_str()),
+                Style::default().fg(color),
+            ),
+        ]));
+    }
+
+    let middle = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)].as_ref())
+        .split(chunks[2]);
+    let tabs = Tabs::new(View::ALL.iter().map(|v| v.title()).collect::<Vec<_>>())
+        .select(View::ALL.iter().position(|v| *v == app.view).unwrap_or(0))
+        .style(Style::default().fg(Color::Gray))
+        .highlight_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD));
+    f.render_widget(tabs, middle[0]);
+
+    match app.view {
+        View::Intel => {
+            let intel = Paragraph::new(intel_lines).block(
+                Block::default()
+                    .title(" 🛡️ INTEL FEED (←/→ tabs) ")
+                    .borders(Borders::ALL),
+            );
+            f.render_widget(intel, middle[1]);
+        }
+        View::Timeline => {
+            let timeline = Paragraph::new(timeline_lines(current)).block(
+                Block::default()
+                    .title(" ⏳ ACCOUNT TIMELINE (←/→ tabs) ")
+                    .borders(Borders::ALL),
+            );
+            f.render_widget(timeline, middle[1]);
+        }
+        View::Diff => {
+            let diff = Paragraph::new(diff_lines(current)).block(
+                Block::default()
+                    .title(" ± SCAN DIFF (←/→ tabs) ")
+                    .borders(Borders::ALL),
+            );
+            f.render_widget(diff, middle[1]);
+        }
+    }
 
     // Scan progress
     if app.scanning {
@@ -300,7 +443,7 @@ fn draw_ui(f: &mut ratatui::Frame, app: &App) {
 
     let input = Paragraph::new(app.input.as_str()).block(
         Block::default()
-            .title(" 🎯 ENTER TARGET ID ")
+            .title(" 🎯 ENTER TARGET ID (:load <file>) ")
             .borders(Borders::ALL),
     );
     f.render_widget(input, bottom_chunks[0]);
@@ -326,3 +469,125 @@ fn draw_ui(f: &mut ratatui::Frame, app: &App) {
     );
     f.render_widget(logs, bottom_chunks[1]);
 }
+
+/// `root -> github -> child` chain for a target, following parents in the app.
+fn lineage(app: &App, target: &Target) -> String {
+    let mut chain = vec![target.id.clone()];
+    let mut current = target;
+    while let Some(p) = &current.provenance {
+        chain.push(format!("{} ({})", p.parent, p.provider));
+        match app.targets.iter().find(|t| t.id == p.parent) {
+            Some(parent) if chain.len() <= app.targets.len() => current = parent,
+            _ => break,
+        }
+    }
+    if chain.len() == 1 {
+        return "root".to_string();
+    }
+    chain.reverse();
+    chain.join(" -> ")
+}
+
+fn diff_lines(target: &Target) -> Vec<Line<'static>> {
+    let (Some(before), Some(after)) = (&target.previous, &target.result) else {
+        return vec![Line::from(Span::styled(
+            "Scan the target twice (or open a case with history) to see changes",
+            Style::default().fg(Color::DarkGray),
+        ))];
+    };
+    let diff = match diff(before, after, None, None) {
+        Ok(diff) => diff,
+        Err(e) => return vec![Line::from(e.to_string())],
+    };
+    let mut lines = Vec::new();
+    if let (Some(from), Some(to)) = (target.previous_at, target.scanned_at) {
+        lines.push(Line::from(Span::styled(
+            format!("{} -> {}", local_time(from), local_time(to)),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    if diff.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No changes since the previous scan",
+            Style::default().fg(Color::Green),
+        )));
+        return lines;
+    }
+    lines.extend(diff.changes.iter().zip(diff.lines()).map(|(change, line)| {
+        let color = match change.change {
+            ChangeKind::Appeared => Color::Green,
+            ChangeKind::Disappeared => Color::Red,
+            ChangeKind::Changed => Color::Yellow,
+        };
+        Line::from(Span::styled(line, Style::default().fg(color)))
+    }));
+    lines
+}
+
+fn local_time(at: DateTime<Utc>) -> String {
+    at.with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
+fn timeline_lines(target: &Target) -> Vec<Line<'static>> {
+    let Some(result) = &target.result else {
+        return vec![Line::from(Span::styled(
+            "Scan the target to build its timeline",
+            Style::default().fg(Color::DarkGray),
+        ))];
+    };
+    let timeline = Timeline::build(result, Utc::now(), ActivityWindow::default());
+    if timeline.events.is_empty() {
+        return vec![Line::from(Span::styled(
+            "No account timestamps exposed by providers",
+            Style::default().fg(Color::DarkGray),
+        ))];
+    }
+
+    let mut lines = Vec::new();
+    if let Some(established) = timeline.established {
+        lines.push(Line::from(vec![
+            Span::styled("Established: ", Style::default().fg(Color::White)),
+            Span::styled(
+                established.format("%Y-%m-%d").to_string(),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+        ]));
+    }
+    for event in &timeline.events {
+        lines.push(Line::from(vec![
+            Span::styled(
+                event.at.format("%Y-%m-%d ").to_string(),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::styled(
+                format!("{:<12} ", event.provider),
+                Style::default().fg(Color::Green),
+            ),
+            Span::raw(event.kind.as_str()),
+        ]));
+    }
+    let join = |accounts: Vec<&str>| {
+        if accounts.is_empty() {
+            "None".to_string()
+        } else {
+            accounts.join(", ")
+        }
+    };
+    lines.push(Line::from(vec![
+        Span::styled("Recent: ", Style::default().fg(Color::White)),
+        Span::styled(
+            join(timeline.recent().map(|a| a.provider.as_str()).collect()),
+            Style::default().fg(Color::Green),
+        ),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("Dormant: ", Style::default().fg(Color::White)),
+        Span::styled(
+            join(timeline.dormant().map(|a| a.provider.as_str()).collect()),
+            Style::default().fg(Color::Yellow),
+        ),
+    ]));
+    lines
+}
@@ -0,0 +1,140 @@
+use std::fs;
+
+use bloody_falcon::{
+    core::{
+        case::Case,
+        crypto::KeySource,
+        engine::{ReconResult, TargetKind},
+    },
+    ui::app::App,
+};
+
+fn result(target: &str, platforms: &[&str]) -> ReconResult {
+    ReconResult {
+        target: target.into(),
+        hits: platforms.len(),
+        platforms: platforms.iter().map(|p| p.to_string()).collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn scans_are_appended_not_overwritten() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    let mut case = Case::create(dir, "nightjar", None).unwrap();
+    assert!(Case::create(dir, "nightjar", None).is_err());
+    case.add_target(TargetKind::Username, "shadow", Some("primary".into()))
+        .unwrap();
+    case.record_scan(&result("shadow", &["github"])).unwrap();
+    case.record_scan(&result("shadow", &["github", "reddit"]))
+        .unwrap();
+    case.add_note(
+        TargetKind::Username,
+        "shadow",
+        "same avatar as forum account",
+    )
+    .unwrap();
+
+    let reopened = Case::open(dir, "nightjar", None).unwrap();
+    let target = reopened.target(TargetKind::Username, "shadow").unwrap();
+    assert_eq!(target.label.as_deref(), Some("primary"));
+    assert_eq!(target.scans.len(), 2);
+    assert_eq!(target.scans[0].result.platforms, vec!["github"]);
+    assert_eq!(target.last_scan().unwrap().result.hits, 2);
+    assert_eq!(target.notes[0].text, "same avatar as forum account");
+
+    let listed = Case::list(dir).unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].name, "nightjar");
+    assert!(Case::open(dir, "../escape", None).is_err());
+}
+
+#[test]
+fn encrypted_case_needs_key() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    let key = KeySource::Passphrase("hunter2".into());
+    let mut case = Case::create(dir, "sealed", Some(key.clone())).unwrap();
+    case.record_scan(&result("shadow", &["github"])).unwrap();
+
+    let raw = fs::read_to_string(dir.join("sealed.jsonl")).unwrap();
+    assert!(!raw.contains("shadow"));
+    assert!(Case::open(dir, "sealed", None).is_err());
+    assert!(Case::open(dir, "sealed", Some(KeySource::Passphrase("nope".into()))).is_err());
+    let reopened = Case::open(dir, "sealed", Some(key)).unwrap();
+    assert_eq!(reopened.targets()[0].scans.len(), 1);
+}
+
+#[test]
+fn plaintext_case_is_encrypted_only_on_write() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    let path = dir.join("open.jsonl");
+    let mut case = Case::create(dir, "open", None).unwrap();
+    case.record_scan(&result("shadow", &["github"])).unwrap();
+    let plain = fs::read_to_string(&path).unwrap();
+
+    // Reading with a key leaves the file alone...
+    let key = KeySource::Passphrase("hunter2".into());
+    let mut case = Case::open(dir, "open", Some(key.clone())).unwrap();
+    assert!(!case.is_encrypted());
+    assert_eq!(fs::read_to_string(&path).unwrap(), plain);
+
+    // ...the next change seals the whole case.
+    case.add_note(TargetKind::Username, "shadow", "alt of ghost")
+        .unwrap();
+    assert!(case.is_encrypted());
+    assert!(!fs::read_to_string(&path).unwrap().contains("shadow"));
+    let reopened = Case::open(dir, "open", Some(key)).unwrap();
+    assert_eq!(reopened.targets()[0].scans.len(), 1);
+    assert_eq!(reopened.targets()[0].notes.len(), 1);
+}
+
+#[test]
+fn unreadable_records_fail_but_torn_tail_is_dropped() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    let path = dir.join("sealed.jsonl");
+    let key = KeySource::Passphrase("hunter2".into());
+    let mut case = Case::create(dir, "sealed", Some(key.clone())).unwrap();
+    case.record_scan(&result("shadow", &["github"])).unwrap();
+    case.record_scan(&result("shadow", &["github", "reddit"]))
+        .unwrap();
+    let intact = fs::read_to_string(&path).unwrap();
+
+    // A crash mid-append leaves a torn line: it is dropped and appends go on.
+    fs::write(&path, format!("{}dGVhcg", intact)).unwrap();
+    let mut reopened = Case::open(dir, "sealed", Some(key.clone())).unwrap();
+    assert_eq!(reopened.targets()[0].scans.len(), 2);
+    reopened.record_scan(&result("shadow", &[])).unwrap();
+    let reopened = Case::open(dir, "sealed", Some(key.clone())).unwrap();
+    assert_eq!(reopened.targets()[0].scans.len(), 3);
+
+    // A complete record that fails authentication drops no history silently.
+    let mut lines: Vec<String> = intact.lines().map(str::to_string).collect();
+    lines[2].replace_range(40..44, "AAAA");
+    fs::write(&path, lines.join("\n") + "\n").unwrap();
+    assert!(Case::open(dir, "sealed", Some(key)).is_err());
+}
+
+#[test]
+fn app_restores_and_records_case_targets() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    let mut case = Case::create(dir, "tui", None).unwrap();
+    case.record_scan(&result("ghost", &["steam"])).unwrap();
+
+    let mut app = App::new();
+    app.add_target("shadow".into());
+    app.attach_case(case);
+    assert_eq!(app.targets.len(), 2);
+    assert_eq!(app.targets[1].platforms, vec!["steam"]);
+
+    let (idx, _, _) = app.start_scan().unwrap();
+    app.complete_scan(idx, result("shadow", &["github"]));
+
+    let reopened = Case::open(dir, "tui", None).unwrap();
+    let shadow = reopened.target(TargetKind::Username, "shadow").unwrap();
+    assert_eq!(shadow.scans.len(), 1);
+}
@@ -0,0 +1,97 @@
+use bloody_falcon::core::engine::ProfileHit;
+use bloody_falcon::modules::crosslink::similarity::{score_profiles, Verdict};
+use bloody_falcon::modules::recon::metadata::{extract_metadata, ProfileMetadata};
+
+fn profile(provider: &str, name: &str, bio: &str, location: Option<&str>) -> ProfileHit {
+    ProfileHit {
+        provider: provider.into(),
+        url: format!("https://{}.example/shadow", provider),
+        linked: vec![],
+        metadata: Some(ProfileMetadata {
+            display_name: Some(name.into()),
+            bio: Some(bio.into()),
+            location: location.map(Into::into),
+            ..Default::default()
+        }),
+    }
+}
+
+#[test]
+fn metadata_from_meta_tags_and_json() {
+    let body = r#"<html><head><title>ignored</title>
+        <meta property="og:title" content="Jane &amp; Doe · GitHub">
+        <meta name='description' content="Rust hacker, CTF player">
+        </head><script>{"location": "Berlin, DE"}</script></html>"#;
+    let meta = extract_metadata(body, "github").unwrap();
+    assert_eq!(meta.display_name.as_deref(), Some("Jane & Doe"));
+    assert_eq!(meta.bio.as_deref(), Some("Rust hacker, CTF player"));
+    assert_eq!(meta.location.as_deref(), Some("Berlin, DE"));
+    assert!(meta.created_at.is_none());
+    assert!(extract_metadata("<html></html>", "github").is_none());
+
+    // The scanned handle is not a display name.
+    let handle = r#"<meta property="profile:username" content="shadow">"#;
+    assert!(extract_metadata(handle, "github").is_none());
+
+    // Only a trailing segment naming the site is dropped.
+    let name = |title: &str, site: &str| {
+        extract_metadata(&format!("<title>{}</title>", title), site).and_then(|m| m.display_name)
+    };
+    assert_eq!(
+        name("Dancing on Ice", "twitter").as_deref(),
+        Some("Dancing on Ice")
+    );
+    assert_eq!(
+        name("Dancing on Ice on Twitter", "twitter").as_deref(),
+        Some("Dancing on Ice")
+    );
+    assert_eq!(
+        name("Jean-Luc - Reddit.com", "reddit").as_deref(),
+        Some("Jean-Luc")
+    );
+    assert_eq!(
+        name("Jean-Luc - Profile", "reddit").as_deref(),
+        Some("Jean-Luc - Profile")
+    );
+    let body = r#"<meta property="og:site_name" content="Stack Overflow"><title>User jd - Stack Overflow</title>"#;
+    assert_eq!(
+        extract_metadata(body, "so")
+            .and_then(|m| m.display_name)
+            .as_deref(),
+        Some("User jd")
+    );
+}
+
+#[test]
+fn similarity_separates_persona_from_collision() {
+    let profiles = vec![
+        profile(
+            "github",
+            "Jane Doe",
+            "rust hacker and ctf player",
+            Some("Berlin"),
+        ),
+        profile(
+            "reddit",
+            "jane doe",
+            "ctf player, rust hacker",
+            Some("Berlin, Germany"),
+        ),
+        profile("steam", "xXsniperXx", "pro gamer", None),
+    ];
+    let scores = score_profiles(&profiles);
+    assert_eq!(scores.len(), 3);
+
+    let same = &scores[0];
+    assert_eq!((same.a.as_str(), same.b.as_str()), ("github", "reddit"));
+    assert_eq!(same.verdict(), Verdict::LikelySame);
+    assert_eq!(same.verdict().as_str(), "likely same");
+
+    let collision = &scores[1];
+    assert_eq!(
+        (collision.a.as_str(), collision.b.as_str()),
+        ("github", "steam")
+    );
+    assert!(collision.location.is_none());
+    assert_eq!(collision.verdict(), Verdict::Unlikely);
+}
@@ -0,0 +1,134 @@
+use std::fs;
+
+use bloody_falcon::{
+    commands::rekey::{self, NewKey, RekeyArgs},
+    config::AppConfig,
+    core::{
+        case::Case,
+        crypto::{read_file, read_input, write_file, KeySource},
+        disk_cache::DiskCache,
+        engine::{ReconResult, TargetKind},
+        evidence::{Capture, EvidenceVault},
+    },
+    modules::watch::WatchState,
+};
+
+#[test]
+fn sealed_files_reject_plaintext_and_tampered_headers() {
+    let tmp = tempfile::tempdir().unwrap();
+    let key = KeySource::Passphrase("hunter2".into());
+    let sealed = tmp.path().join("state.json");
+    write_file(&sealed, b"{\"target\":\"shadow\"}", Some(&key)).unwrap();
+    assert!(!fs::read_to_string(&sealed).unwrap().contains("shadow"));
+    assert_eq!(
+        read_file(&sealed, Some(&key)).unwrap(),
+        b"{\"target\":\"shadow\"}"
+    );
+    assert!(read_file(&sealed, None).is_err());
+
+    // A plain file where a sealed one is expected is not trusted...
+    let plain = tmp.path().join("plain.json");
+    fs::write(&plain, b"{\"target\":\"ghost\"}").unwrap();
+    assert!(read_file(&plain, Some(&key)).is_err());
+    assert!(read_file(&plain, None).is_ok());
+    // ...unless it was named as input.
+    assert!(read_input(&plain, Some(&key)).is_ok());
+
+    // The header is authenticated along with the body.
+    let raw = fs::read_to_string(&sealed).unwrap();
+    let (header, body) = raw.split_once('\n').unwrap();
+    let mut header: serde_json::Value = serde_json::from_str(header).unwrap();
+    header["format"] = "bloody-falcon-sealed".into();
+    header["extra"] = true.into();
+    fs::write(&sealed, format!("{}\n{}", header, body)).unwrap();
+    assert!(read_file(&sealed, Some(&key)).is_err());
+}
+
+#[test]
+fn rekey_moves_every_sealed_artifact() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    let key_file = |name: &str, fill: u8| {
+        let path = dir.join(name);
+        fs::write(&path, [fill; 32]).unwrap();
+        path
+    };
+    let (old_path, new_path) = (key_file("old.key", b'a'), key_file("new.key", b'b'));
+    let old = KeySource::KeyFile(old_path.clone());
+    let new = KeySource::KeyFile(new_path.clone());
+    let cfg = AppConfig {
+        key_file: Some(old_path.to_string_lossy().into_owned()),
+        disk_cache_path: dir.join("cache.log").to_string_lossy().into_owned(),
+        case_dir: dir.join("cases").to_string_lossy().into_owned(),
+        watch_state_path: dir.join("watch.json").to_string_lossy().into_owned(),
+        evidence_dir: dir.join("evidence").to_string_lossy().into_owned(),
+        ..AppConfig::default()
+    };
+    let shadow = ReconResult {
+        target: "shadow".into(),
+        hits: 1,
+        platforms: vec!["github".into()],
+        ..Default::default()
+    };
+
+    let cache = DiskCache::open(cfg.disk_cache_path.as_ref(), Some(old.clone())).unwrap();
+    cache.put("github:shadow", &shadow).unwrap();
+    drop(cache);
+    let mut case = Case::create(cfg.case_dir.as_ref(), "nightjar", Some(old.clone())).unwrap();
+    case.add_target(TargetKind::Username, "shadow", None)
+        .unwrap();
+    WatchState::default()
+        .save(cfg.watch_state_path.as_ref(), Some(&old))
+        .unwrap();
+    let vault = EvidenceVault::new(&cfg, Some(old.clone()));
+    let capture = Capture {
+        url: "https://example.com/shadow".into(),
+        final_url: "https://example.com/shadow".into(),
+        status: 200,
+        headers: Vec::new(),
+        body: b"shadow".to_vec(),
+        truncated: false,
+    };
+    let evidence = vault.store("shadow", "github", &capture).unwrap();
+    let output = dir.join("out.json");
+    write_file(&output, b"{\"target\":\"shadow\"}", Some(&old)).unwrap();
+
+    let args = RekeyArgs {
+        new_key: NewKey {
+            new_key_file: Some(new_path),
+            new_passphrase_env: None,
+            decrypt: false,
+        },
+        files: vec![output.clone()],
+    };
+    let report = rekey::run(&args, &cfg).unwrap();
+    assert!(report.contains("re-encrypted case nightjar"), "{}", report);
+    assert!(report.contains("1 evidence capture(s)"), "{}", report);
+
+    // Everything opens with the new key only.
+    let cache_path: &std::path::Path = cfg.disk_cache_path.as_ref();
+    assert!(DiskCache::open(cache_path, Some(old.clone())).is_err());
+    let cache = DiskCache::open(cache_path, Some(new.clone())).unwrap();
+    assert_eq!(cache.len().unwrap(), 1);
+    drop(cache);
+    assert!(Case::open(cfg.case_dir.as_ref(), "nightjar", Some(old.clone())).is_err());
+    assert!(Case::open(cfg.case_dir.as_ref(), "nightjar", Some(new.clone())).is_ok());
+    assert!(WatchState::load(cfg.watch_state_path.as_ref(), Some(&old)).is_err());
+    assert!(WatchState::load(cfg.watch_state_path.as_ref(), Some(&new)).is_ok());
+    assert!(EvidenceVault::new(&cfg, Some(old))
+        .load(&evidence.sha256)
+        .is_err());
+    let moved = EvidenceVault::new(&cfg, Some(new.clone()));
+    assert_eq!(
+        moved.load(&evidence.sha256).unwrap().body().unwrap(),
+        b"shadow"
+    );
+    assert_eq!(
+        read_file(&output, Some(&new)).unwrap(),
+        b"{\"target\":\"shadow\"}"
+    );
+
+    // A second run finds everything already moved.
+    let report = rekey::run(&args, &cfg).unwrap();
+    assert!(!report.contains("re-encrypted"), "{}", report);
+}
@@ -0,0 +1,120 @@
+use bloody_falcon::{
+    commands::diff::{against_cache, from_case},
+    config::{AppConfig, ProviderConfig},
+    core::{
+        case::Case,
+        engine::{Engine, ReconResult, TargetKind},
+    },
+    modules::diff::{diff, ChangeKind, ProviderState},
+};
+use httpmock::prelude::*;
+
+fn scan(platforms: &[&str], restricted: &[&str], failed: &[&str]) -> ReconResult {
+    let list = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    ReconResult {
+        target: "shadow".into(),
+        hits: platforms.len(),
+        platforms: list(platforms),
+        restricted: list(restricted),
+        failed: failed.iter().map(|p| format!("{}: timeout", p)).collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn diff_classifies_provider_changes() {
+    let before = scan(&["github", "reddit", "steam"], &[], &["twitter"]);
+    let after = scan(&["github", "psnprofiles"], &["steam"], &[]);
+    let changed = diff(&before, &after, None, None).unwrap();
+
+    let changes: Vec<(&str, ChangeKind)> = changed
+        .changes
+        .iter()
+        .map(|c| (c.provider.as_str(), c.change))
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            ("psnprofiles", ChangeKind::Appeared),
+            ("reddit", ChangeKind::Disappeared),
+            ("steam", ChangeKind::Changed),
+            ("twitter", ChangeKind::Changed),
+        ]
+    );
+    assert_eq!(changed.changes[2].after, Some(ProviderState::Restricted));
+    assert_eq!(changed.unchanged, vec!["github"]);
+    assert!(changed
+        .to_text()
+        .contains("+ psnprofiles appeared (none -> hit)"));
+
+    // A hit coming back after a failed check is not a new account.
+    let recovered = diff(
+        &scan(&[], &[], &["github"]),
+        &scan(&["github"], &[], &[]),
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(recovered.changes[0].change, ChangeKind::Changed);
+
+    let mut other = after.clone();
+    other.target = "ghost".into();
+    assert!(diff(&before, &other, None, None).is_err());
+}
+
+#[test]
+fn diff_reads_case_history() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut case = Case::create(tmp.path(), "diffs", None).unwrap();
+    case.record_scan(&scan(&["github"], &[], &[])).unwrap();
+    assert!(from_case(&case, TargetKind::Username, "shadow", None, None).is_err());
+    case.record_scan(&scan(&["github", "reddit"], &[], &[]))
+        .unwrap();
+    case.record_scan(&scan(&["reddit"], &[], &[])).unwrap();
+
+    let latest = from_case(&case, TargetKind::Username, "shadow", None, None).unwrap();
+    assert_eq!(latest.disappeared().count(), 1);
+    let first_to_last = from_case(&case, TargetKind::Username, "shadow", Some(1), Some(3)).unwrap();
+    assert_eq!(first_to_last.appeared().next().unwrap().provider, "reddit");
+    assert!(from_case(&case, TargetKind::Username, "shadow", Some(0), None).is_err());
+}
+
+#[tokio::test]
+async fn cache_diff_skips_providers_missing_from_the_cache() {
+    let server = MockServer::start();
+    let kept = server.mock(|when, then| {
+        when.method(GET).path("/kept/shadow");
+        then.status(200);
+    });
+    let mut flaky = server.mock(|when, then| {
+        when.method(GET).path("/flaky/shadow");
+        then.status(503);
+    });
+    let provider = |name: &str| ProviderConfig {
+        name: name.into(),
+        enabled: true,
+        base_url: format!("{}/{}/{{username}}", server.base_url(), name),
+    };
+    let cfg = AppConfig {
+        cache_ttl_seconds: 600,
+        disk_cache_enabled: false,
+        providers: vec![provider("kept"), provider("flaky")],
+        ..AppConfig::default()
+    };
+    let engine = Engine::new(cfg).unwrap();
+    engine.scan_username("shadow", true).await.unwrap();
+
+    // The failed check was not cached; live, the account is there.
+    flaky.delete();
+    flaky = server.mock(|when, then| {
+        when.method(GET).path("/flaky/shadow");
+        then.status(200);
+    });
+    let changed = against_cache(&engine, TargetKind::Username, "shadow")
+        .await
+        .unwrap();
+    assert!(changed.changes.is_empty(), "{:?}", changed.changes);
+    assert_eq!(changed.unchanged, vec!["kept"]);
+    kept.assert_hits(2);
+    flaky.assert_hits(1);
+}
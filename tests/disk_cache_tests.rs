@@ -0,0 +1,300 @@
+use std::{
+    fs,
+    io::Write,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use bloody_falcon::{
+    commands::cache::{parse_age, run, CacheCommand},
+    config::AppConfig,
+    core::{crypto::KeySource, disk_cache::DiskCache, engine::ReconResult},
+};
+
+fn result(target: &str, hits: usize) -> ReconResult {
+    ReconResult {
+        target: target.into(),
+        hits,
+        ..Default::default()
+    }
+}
+
+const TTL: Duration = Duration::from_secs(600);
+
+#[test]
+fn log_survives_reopen_and_compaction() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("cache.log");
+    let cache = DiskCache::new(&path).unwrap();
+    for i in 0..100 {
+        cache.put("shadow", &result("shadow", i)).unwrap();
+    }
+    cache.put("ghost", &result("ghost", 1)).unwrap();
+
+    // Overwrites were compacted away rather than growing the log forever.
+    assert!(fs::read_to_string(&path).unwrap().lines().count() < 100);
+
+    let reopened = DiskCache::new(&path).unwrap();
+    assert_eq!(reopened.len().unwrap(), 2);
+    assert_eq!(reopened.get("shadow", TTL).unwrap().unwrap().hits, 99);
+
+    // A second handle sees appends made through the first.
+    cache.put("late", &result("late", 3)).unwrap();
+    assert_eq!(reopened.get("late", TTL).unwrap().unwrap().hits, 3);
+
+    assert_eq!(reopened.purge_expired(Duration::ZERO).unwrap(), 3);
+    assert_eq!(cache.len().unwrap(), 0);
+}
+
+/// Write a whole-file JSON cache as older versions did.
+fn write_legacy(path: &std::path::Path) {
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    let map = serde_json::json!({
+        "shadow": {
+            "result": {"hits": 1, "platforms": ["github"], "failed": [], "restricted": [], "rate_limited": []},
+            "timestamp_ms": now_ms as u64
+        }
+    });
+    fs::write(path, serde_json::to_string_pretty(&map).unwrap()).unwrap();
+}
+
+#[test]
+fn legacy_json_cache_is_migrated() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    let legacy = dir.join("cache.json");
+    write_legacy(&legacy);
+
+    let cache = DiskCache::new(&dir.join("cache.log")).unwrap();
+    let hit = cache.get("shadow", TTL).unwrap().unwrap();
+    assert_eq!(hit.platforms, vec!["github"]);
+    assert!(!legacy.exists());
+    assert!(dir.join("cache.json.migrated").exists());
+}
+
+#[test]
+fn keyed_migration_leaves_no_plaintext() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    write_legacy(&dir.join("cache.json"));
+    let key = KeySource::Passphrase("correct horse".into());
+
+    let cache = DiskCache::open(&dir.join("cache.log"), Some(key)).unwrap();
+    assert!(cache.get("shadow", TTL).unwrap().is_some());
+    assert!(dir.join("cache.json.migrated").exists());
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        let raw = String::from_utf8_lossy(&fs::read(&path).unwrap()).into_owned();
+        assert!(
+            !raw.contains("shadow"),
+            "{} holds plaintext",
+            path.display()
+        );
+    }
+}
+
+#[test]
+fn unreadable_cache_fails_closed() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("cache.log");
+    fs::write(&path, "not a cache").unwrap();
+    assert!(DiskCache::new(&path).is_err());
+    assert_eq!(fs::read_to_string(&path).unwrap(), "not a cache");
+}
+
+#[test]
+fn torn_tail_is_skipped() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("cache.log");
+    let cache = DiskCache::new(&path).unwrap();
+    cache.put("shadow", &result("shadow", 1)).unwrap();
+    let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+    file.write_all(br#"{"op":"put","key":"half"#).unwrap();
+
+    let reopened = DiskCache::new(&path).unwrap();
+    reopened.put("ghost", &result("ghost", 2)).unwrap();
+    assert_eq!(reopened.len().unwrap(), 2);
+    assert!(reopened.get("half", TTL).unwrap().is_none());
+}
+
+#[test]
+fn cache_commands_export_purge_import() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    let cache = DiskCache::new(&dir.join("cache.log")).unwrap();
+    cache.put("github:shadow", &result("shadow", 1)).unwrap();
+    cache.put("reddit:shadow", &result("shadow", 0)).unwrap();
+    cache.put("github:ghost", &result("ghost", 1)).unwrap();
+    let cfg = AppConfig::default();
+
+    let stats = run(&CacheCommand::Stats, &cache, &cfg).unwrap();
+    assert!(stats.contains("entries: 3 (2 targets)"), "{stats}");
+    let shown = run(
+        &CacheCommand::Show {
+            target: "shadow".into(),
+        },
+        &cache,
+        &cfg,
+    )
+    .unwrap();
+    assert!(shown.contains("reddit:shadow") && !shown.contains("github:ghost"));
+
+    let export = dir.join("export.jsonl");
+    run(
+        &CacheCommand::Export {
+            output: Some(export.clone()),
+        },
+        &cache,
+        &cfg,
+    )
+    .unwrap();
+    let purge = CacheCommand::Purge {
+        older_than: Some(parse_age("0s").unwrap()),
+    };
+    assert_eq!(
+        run(&purge, &cache, &cfg).unwrap(),
+        "purged 3 entries, 0 remaining\n"
+    );
+
+    run(&CacheCommand::Import { path: export }, &cache, &cfg).unwrap();
+    assert_eq!(cache.len().unwrap(), 3);
+    assert_eq!(cache.get("github:ghost", TTL).unwrap().unwrap().hits, 1);
+    assert_eq!(parse_age("7d").unwrap(), Duration::from_secs(7 * 86_400));
+    assert!(parse_age("7w").is_err());
+    assert!(parse_age("999999999999999d").is_err());
+    assert!(parse_age("99999999999999999999").is_err());
+}
+
+#[test]
+fn encrypted_cache_fails_closed_and_rekeys() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    let path = dir.join("cache.log");
+    let right = KeySource::Passphrase("correct horse".into());
+    let wrong = KeySource::Passphrase("battery staple".into());
+
+    let cache = DiskCache::open(&path, Some(right.clone())).unwrap();
+    cache.put("github:shadow", &result("shadow", 1)).unwrap();
+    assert!(cache.is_encrypted());
+    assert!(!fs::read_to_string(&path).unwrap().contains("shadow"));
+    // The KDF cost is pinned in the header rather than taken from crate defaults.
+    let raw = fs::read_to_string(&path).unwrap();
+    let header: serde_json::Value = serde_json::from_str(raw.lines().next().unwrap()).unwrap();
+    assert_eq!(header["encryption"]["kdf"], "argon2id");
+    assert_eq!(header["encryption"]["m_cost"], 19 * 1024);
+    assert_eq!(header["encryption"]["t_cost"], 2);
+    assert_eq!(header["encryption"]["version"], 0x13);
+
+    assert!(DiskCache::new(&path).is_err());
+    assert!(DiskCache::open(&path, Some(wrong.clone())).is_err());
+    let reopened = DiskCache::open(&path, Some(right)).unwrap();
+    assert_eq!(reopened.get("github:shadow", TTL).unwrap().unwrap().hits, 1);
+
+    reopened.rekey(Some(&wrong)).unwrap();
+    let rekeyed = DiskCache::open(&path, Some(wrong)).unwrap();
+    assert_eq!(rekeyed.len().unwrap(), 1);
+    rekeyed.rekey(None).unwrap();
+    assert!(fs::read_to_string(&path).unwrap().contains("shadow"));
+    assert!(DiskCache::new(&path)
+        .unwrap()
+        .get("github:shadow", TTL)
+        .unwrap()
+        .is_some());
+}
+
+#[test]
+fn tampered_sealed_log_fails_closed() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("cache.log");
+    let key = KeySource::Passphrase("correct horse".into());
+    let cache = DiskCache::open(&path, Some(key.clone())).unwrap();
+    cache.put("github:shadow", &result("shadow", 1)).unwrap();
+    cache.put("reddit:shadow", &result("shadow", 0)).unwrap();
+    let sealed = fs::read_to_string(&path).unwrap();
+    let (header, body) = sealed.split_once('\n').unwrap();
+
+    // Stripping the encryption field must not turn the log into an empty
+    // plaintext one that then gets re-encrypted.
+    let mut stripped: serde_json::Value = serde_json::from_str(header).unwrap();
+    stripped.as_object_mut().unwrap().remove("encryption");
+    let stripped = format!("{}\n{}", stripped, body);
+    fs::write(&path, &stripped).unwrap();
+    assert!(DiskCache::new(&path).is_err());
+    assert!(DiskCache::open(&path, Some(key.clone())).is_err());
+    assert_eq!(fs::read_to_string(&path).unwrap(), stripped);
+
+    // A record that fails authentication is an error, not a skipped line.
+    let lines: Vec<&str> = body.lines().collect();
+    let mut forged = lines[0].to_string();
+    forged.replace_range(40..44, "AAAA");
+    fs::write(&path, format!("{}\n{}\n{}\n", header, forged, lines[1])).unwrap();
+    assert!(DiskCache::open(&path, Some(key.clone())).is_err());
+
+    // Records are bound to their header: a new generation rejects them.
+    let mut moved: serde_json::Value = serde_json::from_str(header).unwrap();
+    moved["generation"] = 1.into();
+    fs::write(&path, format!("{}\n{}", moved, body)).unwrap();
+    assert!(DiskCache::open(&path, Some(key)).is_err());
+}
+
+#[test]
+fn plaintext_cache_is_encrypted_on_first_write_with_key() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    let path = dir.join("cache.log");
+    DiskCache::new(&path)
+        .unwrap()
+        .put("github:shadow", &result("shadow", 1))
+        .unwrap();
+    let key = dir.join("cache.key");
+    fs::write(&key, b"0123456789abcdef0123456789abcdef").unwrap();
+
+    // Reading leaves the file as it is...
+    let cache = DiskCache::open(&path, Some(KeySource::KeyFile(key))).unwrap();
+    assert!(cache.get("github:shadow", TTL).unwrap().is_some());
+    assert!(!cache.is_encrypted());
+    assert!(fs::read_to_string(&path).unwrap().contains("shadow"));
+
+    // ...the first write encrypts it, old entries included.
+    cache.put("reddit:ghost", &result("ghost", 0)).unwrap();
+    assert!(cache.is_encrypted());
+    let raw = fs::read_to_string(&path).unwrap();
+    assert!(!raw.contains("shadow") && !raw.contains("ghost"));
+    assert_eq!(cache.len().unwrap(), 2);
+}
+
+#[test]
+fn concurrent_writers_keep_every_entry() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    let path = dir.join("cache.log");
+    // Separate handles lock like separate processes would.
+    let writers: Vec<_> = (0..4)
+        .map(|w| {
+            let path = path.clone();
+            std::thread::spawn(move || {
+                let cache = DiskCache::new(&path).unwrap();
+                for i in 0..50 {
+                    let key = format!("w{}-{}", w, i);
+                    // Overwrites pile up dead records and force compactions mid-run.
+                    cache.put(&key, &result(&key, 0)).unwrap();
+                    cache.put(&key, &result(&key, 1)).unwrap();
+                }
+            })
+        })
+        .collect();
+    for writer in writers {
+        writer.join().unwrap();
+    }
+
+    let cache = DiskCache::new(&path).unwrap();
+    assert_eq!(cache.len().unwrap(), 200);
+    assert!(cache
+        .entries()
+        .unwrap()
+        .iter()
+        .all(|entry| entry.result.hits == 1));
+    assert!(!dir.join("cache.tmp").exists());
+}
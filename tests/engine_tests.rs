@@ -1,5 +1,7 @@
 use bloody_falcon::config::{AppConfig, ProviderConfig};
-use bloody_falcon::core::engine::Engine;
+use bloody_falcon::core::engine::{Engine, TargetKind};
+use bloody_falcon::modules::crosslink::pivot::PivotPolicy;
+use bloody_falcon::modules::recon::domain::endpoint_url;
 use httpmock::prelude::*;
 
 #[tokio::test]
@@ -22,6 +24,7 @@ async fn engine_hits_mock_provider() {
             enabled: true,
             base_url: format!("{}/user/{{username}}", server.base_url()),
         }],
+        ..AppConfig::default()
     };
 
     let engine = Engine::new(cfg).unwrap();
@@ -29,3 +32,354 @@ async fn engine_hits_mock_provider() {
     assert_eq!(res.hits, 1);
     assert_eq!(res.platforms, vec!["mock"]);
 }
+
+#[tokio::test]
+async fn engine_extracts_linked_identifiers() {
+    let server = MockServer::start();
+    let _hit = server.mock(|when, then| {
+        when.method(GET).path("/user/tester");
+        then.status(200).body(
+            r#"<a href="https://twitter.com/tester_alt">tw</a>
+               <a href="https://twitter.com/intent/follow">follow</a>
+               contact: Tester@Example.org <img src="logo@2x.png">"#,
+        );
+    });
+
+    let cfg = AppConfig {
+        timeout_ms: 2000,
+        max_concurrent_requests: 2,
+        cache_ttl_seconds: 0,
+        user_agent: "bf-test".to_string(),
+        disk_cache_enabled: false,
+        disk_cache_path: "data/cache.json".into(),
+        providers: vec![
+            ProviderConfig {
+                name: "mock".into(),
+                enabled: true,
+                base_url: format!("{}/user/{{username}}", server.base_url()),
+            },
+            ProviderConfig {
+                name: "twitter".into(),
+                enabled: false,
+                base_url: "https://twitter.com/{username}".into(),
+            },
+        ],
+        ..AppConfig::default()
+    };
+
+    let engine = Engine::new(cfg).unwrap();
+    let res = engine.scan_username("tester", true).await.unwrap();
+    assert_eq!(res.target, "tester");
+    assert_eq!(res.profiles.len(), 1);
+    let linked: Vec<(&str, Option<&str>)> = res.profiles[0]
+        .linked
+        .iter()
+        .map(|l| (l.value.as_str(), l.provider.as_deref()))
+        .collect();
+    assert_eq!(
+        linked,
+        vec![
+            ("tester_alt", Some("twitter")),
+            ("tester@example.org", None)
+        ]
+    );
+}
+
+#[tokio::test]
+async fn pivot_follows_links_with_depth_and_loop_limits() {
+    let server = MockServer::start();
+    let base = server.base_url();
+    let _root = server.mock(|when, then| {
+        when.method(GET).path("/user/root");
+        then.status(200)
+            .body(format!("<a href=\"{}/user/child\">alt</a>", base));
+    });
+    let _child = server.mock(|when, then| {
+        when.method(GET).path("/user/child");
+        then.status(200).body(format!(
+            "<a href=\"{0}/user/root\">main</a><a href=\"{0}/user/grandchild\">x</a>",
+            base
+        ));
+    });
+
+    let cfg = AppConfig {
+        timeout_ms: 2000,
+        max_concurrent_requests: 2,
+        cache_ttl_seconds: 0,
+        user_agent: "bf-test".to_string(),
+        disk_cache_enabled: false,
+        disk_cache_path: "data/cache.json".into(),
+        providers: vec![ProviderConfig {
+            name: "mock".into(),
+            enabled: true,
+            base_url: format!("{}/user/{{username}}", base),
+        }],
+        ..AppConfig::default()
+    };
+
+    let engine = Engine::new(cfg).unwrap();
+    let policy = PivotPolicy {
+        max_depth: 1,
+        max_targets: 10,
+    };
+    let results = engine
+        .scan_with_pivots(TargetKind::Username, "root", false, policy)
+        .await
+        .unwrap();
+    let targets: Vec<&str> = results.iter().map(|r| r.target.as_str()).collect();
+    assert_eq!(targets, vec!["root", "child"]);
+    assert!(results[0].provenance.is_none());
+    let provenance = results[1].provenance.as_ref().unwrap();
+    assert_eq!(provenance.parent, "root");
+    assert_eq!(provenance.provider, "mock");
+    assert_eq!(provenance.depth, 1);
+}
+
+#[tokio::test]
+async fn domain_scan_joins_rdap_and_ct() {
+    let server = MockServer::start();
+    let _rdap = server.mock(|when, then| {
+        when.method(GET).path("/rdap/domain/example.com");
+        then.status(200).json_body(serde_json::json!({
+            "status": ["active"],
+            "events": [
+                {"eventAction": "registration", "eventDate": "2009-03-14T00:00:00Z"},
+                {"eventAction": "expiration", "eventDate": "2027-03-14T00:00:00Z"}
+            ],
+            "nameservers": [{"ldhName": "NS1.EXAMPLE.NET"}],
+            "entities": [
+                {"roles": ["registrar"], "vcardArray": ["vcard", [["fn", {}, "text", "Example Registrar"]]]},
+                {"roles": ["registrant"], "vcardArray": ["vcard", [["email", {}, "text", "owner@example.com"]]]}
+            ]
+        }));
+    });
+    let _ct = server.mock(|when, then| {
+        when.method(GET).path("/ct").query_param("q", "example.com");
+        then.status(200).json_body(serde_json::json!([
+            {"common_name": "example.com", "name_value": "example.com\nwww.example.com"},
+            {"common_name": "*.dev.example.com", "name_value": "*.dev.example.com"}
+        ]));
+    });
+
+    let cfg = AppConfig {
+        cache_ttl_seconds: 0,
+        rdap_url: format!("{}/rdap/domain/{{domain}}", server.base_url()),
+        ct_url: format!("{}/ct?q={{domain}}", server.base_url()),
+        ..AppConfig::default()
+    };
+
+    let engine = Engine::new(cfg).unwrap();
+    let res = engine
+        .scan_target(TargetKind::Domain, "example.com", false)
+        .await
+        .unwrap();
+    assert_eq!(res.kind, TargetKind::Domain);
+    assert_eq!(res.platforms, vec!["rdap", "ct"]);
+    let domain = res.domain.unwrap();
+    assert_eq!(domain.registrar.as_deref(), Some("Example Registrar"));
+    assert_eq!(domain.nameservers, vec!["ns1.example.net"]);
+    assert_eq!(domain.emails, vec!["owner@example.com"]);
+    assert_eq!(
+        domain.hostnames,
+        vec!["dev.example.com", "example.com", "www.example.com"]
+    );
+    assert_eq!(res.profiles[0].linked[0].value, "owner@example.com");
+    // The registrar is an organisation, not a persona name.
+    let metadata = res.profiles[0].metadata.as_ref().unwrap();
+    assert_eq!(metadata.organization.as_deref(), Some("Example Registrar"));
+    assert!(metadata.display_name.is_none());
+}
+
+#[tokio::test]
+async fn cache_is_per_provider() {
+    let server = MockServer::start();
+    let first = server.mock(|when, then| {
+        when.method(GET).path("/a/tester");
+        then.status(200);
+    });
+    let second = server.mock(|when, then| {
+        when.method(GET).path("/b/tester");
+        then.status(404);
+    });
+
+    let provider = |name: &str| ProviderConfig {
+        name: name.into(),
+        enabled: true,
+        base_url: format!("{}/{}/{{username}}", server.base_url(), name),
+    };
+    let cfg = AppConfig {
+        cache_ttl_seconds: 60,
+        disk_cache_enabled: false,
+        providers: vec![provider("a")],
+        ..AppConfig::default()
+    };
+
+    let mut engine = Engine::new(cfg).unwrap();
+    let res = engine.scan_username("tester", true).await.unwrap();
+    assert!(res.cached.is_empty());
+
+    // Enabling another provider only queries the new one.
+    engine.config.providers.push(provider("b"));
+    let res = engine.scan_username("tester", true).await.unwrap();
+    first.assert_hits(1);
+    second.assert_hits(1);
+    assert_eq!(res.cached, vec!["a"]);
+    assert_eq!(res.platforms, vec!["a"]);
+    assert_eq!(res.profiles.len(), 1);
+}
+
+#[tokio::test]
+async fn transient_failures_are_not_cached() {
+    let server = MockServer::start();
+    let down = server.mock(|when, then| {
+        when.method(GET).path("/down/tester");
+        then.status(503);
+    });
+    let missing = server.mock(|when, then| {
+        when.method(GET).path("/missing/tester");
+        then.status(404);
+    });
+
+    let provider = |name: &str| ProviderConfig {
+        name: name.into(),
+        enabled: true,
+        base_url: format!("{}/{}/{{username}}", server.base_url(), name),
+    };
+    let cfg = AppConfig {
+        cache_ttl_seconds: 60,
+        negative_cache_ttl_seconds: Some(60),
+        disk_cache_enabled: false,
+        providers: vec![provider("down"), provider("missing")],
+        ..AppConfig::default()
+    };
+
+    let engine = Engine::new(cfg).unwrap();
+    let res = engine.scan_username("tester", true).await.unwrap();
+    assert_eq!(res.failed.len(), 1);
+    let res = engine.scan_username("tester", true).await.unwrap();
+    assert_eq!(res.failed.len(), 1);
+    assert_eq!(res.cached, vec!["missing"]);
+    down.assert_hits(2);
+    missing.assert_hits(1);
+}
+
+#[tokio::test]
+async fn zero_ttl_disables_caching_of_every_outcome() {
+    let server = MockServer::start();
+    let missing = server.mock(|when, then| {
+        when.method(GET).path("/missing/tester");
+        then.status(404);
+    });
+    let blocked = server.mock(|when, then| {
+        when.method(GET).path("/blocked/tester");
+        then.status(403);
+    });
+    let provider = |name: &str| ProviderConfig {
+        name: name.into(),
+        enabled: true,
+        base_url: format!("{}/{}/{{username}}", server.base_url(), name),
+    };
+    let cfg = AppConfig {
+        cache_ttl_seconds: 0,
+        disk_cache_enabled: false,
+        providers: vec![provider("missing"), provider("blocked")],
+        ..AppConfig::default()
+    };
+
+    let engine = Engine::new(cfg).unwrap();
+    engine.scan_username("tester", true).await.unwrap();
+    let res = engine.scan_username("tester", true).await.unwrap();
+    assert!(res.cached.is_empty());
+    missing.assert_hits(2);
+    blocked.assert_hits(2);
+}
+
+#[tokio::test]
+async fn provider_changes_invalidate_cached_results() {
+    let server = MockServer::start();
+    let old = server.mock(|when, then| {
+        when.method(GET).path("/old/tester");
+        then.status(200);
+    });
+    let fixed = server.mock(|when, then| {
+        when.method(GET).path("/fixed/tester");
+        then.status(404);
+    });
+
+    let tmp = tempfile::tempdir().unwrap();
+    let cache_path = tmp.path().join("cache.log");
+    let cfg = AppConfig {
+        cache_ttl_seconds: 600,
+        disk_cache_enabled: true,
+        disk_cache_path: cache_path.to_string_lossy().into_owned(),
+        providers: vec![ProviderConfig {
+            name: "mock".into(),
+            enabled: true,
+            base_url: format!("{}/old/{{username}}", server.base_url()),
+        }],
+        ..AppConfig::default()
+    };
+
+    let engine = Engine::new(cfg.clone()).unwrap();
+    assert_eq!(engine.scan_username("tester", true).await.unwrap().hits, 1);
+    drop(engine);
+
+    // Same definition: served from disk without a request.
+    let engine = Engine::new(cfg.clone()).unwrap();
+    let res = engine.scan_username("tester", true).await.unwrap();
+    assert_eq!(res.cached, vec!["mock"]);
+    old.assert_hits(1);
+
+    // Fixed URL template: the stale hit is ignored, on disk and in memory.
+    let mut engine = engine;
+    engine.config.providers[0].base_url = format!("{}/fixed/{{username}}", server.base_url());
+    let res = engine.scan_username("tester", true).await.unwrap();
+    assert!(res.cached.is_empty());
+    assert_eq!(res.hits, 0);
+    fixed.assert_hits(1);
+
+    let mut cfg = cfg;
+    cfg.providers[0].base_url = format!("{}/fixed/{{username}}", server.base_url());
+    let engine = Engine::new(cfg).unwrap();
+    let res = engine.scan_username("tester", true).await.unwrap();
+    assert_eq!(res.cached, vec!["mock"]);
+    assert_eq!(res.hits, 0);
+    fixed.assert_hits(1);
+    old.assert_hits(1);
+}
+
+#[tokio::test]
+async fn malformed_domains_are_rejected_before_any_request() {
+    let server = MockServer::start();
+    let any = server.mock(|when, then| {
+        when.any_request();
+        then.status(200);
+    });
+    let cfg = AppConfig {
+        cache_ttl_seconds: 0,
+        rdap_url: format!("{}/rdap/domain/{{domain}}", server.base_url()),
+        ct_url: format!("{}/ct?q={{domain}}", server.base_url()),
+        ..AppConfig::default()
+    };
+    let engine = Engine::new(cfg).unwrap();
+    for input in [
+        "domain:",
+        "domain:a/b",
+        "domain:x&exclude=expired",
+        "domain:a..b",
+    ] {
+        let (kind, id) = TargetKind::parse_input(input);
+        assert_eq!(kind, TargetKind::Domain);
+        assert!(
+            engine.scan_target(kind, &id, false).await.is_err(),
+            "{}",
+            input
+        );
+    }
+    any.assert_hits(0);
+
+    assert_eq!(
+        endpoint_url("https://crt.sh/?q={domain}", "x&exclude=expired"),
+        "https://crt.sh/?q=x%26exclude%3Dexpired"
+    );
+}
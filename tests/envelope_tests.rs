@@ -0,0 +1,239 @@
+use bloody_falcon::{
+    config::{AppConfig, ElasticsearchConfig, ProviderConfig, SplunkConfig},
+    core::{
+        engine::{ProfileHit, ReconResult, TargetKind},
+        evidence::EvidenceRef,
+    },
+    export::envelope::{parse_results, Envelope, RequestedTarget, SCHEMA_VERSION},
+    modules::{
+        crosslink::{pivot::Provenance, IdentifierKind, LinkedIdentifier},
+        recon::{domain::DomainIntel, metadata::ProfileMetadata},
+    },
+};
+use chrono::Utc;
+use serde_json::Value;
+
+const SCHEMA: &str = include_str!("../docs/schema/result-envelope.v1.json");
+
+/// Checks the schema keywords the envelope schema uses; undeclared object
+/// properties are reported too, so the schema cannot fall behind the output.
+fn validate(schema: &Value, root: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+    if let Some(reference) = schema["$ref"].as_str() {
+        let name = reference.trim_start_matches("#/$defs/");
+        validate(&root["$defs"][name], root, value, path, errors);
+    }
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            errors.push(format!("{}: expected {}", path, expected));
+        }
+    }
+    if let Some(options) = schema["enum"].as_array() {
+        if !options.contains(value) {
+            errors.push(format!("{}: {} not in enum", path, value));
+        }
+    }
+    let types: Vec<&str> = match &schema["type"] {
+        Value::String(t) => vec![t.as_str()],
+        Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+        _ => vec![],
+    };
+    let type_ok = types.is_empty()
+        || types.iter().any(|t| match *t {
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "string" => value.is_string(),
+            "integer" => value.is_u64() || value.is_i64(),
+            "number" => value.is_number(),
+            "boolean" => value.is_boolean(),
+            "null" => value.is_null(),
+            _ => false,
+        });
+    if !type_ok {
+        errors.push(format!("{}: {} is not {:?}", path, value, types));
+    }
+    if let (Some(pattern), Some(text)) = (schema["pattern"].as_str(), value.as_str()) {
+        // Only the config hash carries a pattern.
+        assert_eq!(pattern, "^sha256:[0-9a-f]{64}$");
+        let hex = text.strip_prefix("sha256:").unwrap_or("");
+        if hex.len() != 64 || !hex.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f')) {
+            errors.push(format!("{}: {} does not match {}", path, text, pattern));
+        }
+    }
+    if let Value::Object(fields) = value {
+        for required in schema["required"].as_array().into_iter().flatten() {
+            if !fields.contains_key(required.as_str().unwrap()) {
+                errors.push(format!("{}: missing {}", path, required));
+            }
+        }
+        if let Some(properties) = schema["properties"].as_object() {
+            for (key, field) in fields {
+                match properties.get(key) {
+                    Some(sub) => validate(sub, root, field, &format!("{}.{}", path, key), errors),
+                    None => errors.push(format!("{}: undeclared property {}", path, key)),
+                }
+            }
+        }
+    }
+    if let (Value::Array(items), Some(item_schema)) = (value, schema.get("items")) {
+        for (i, item) in items.iter().enumerate() {
+            validate(item_schema, root, item, &format!("{}[{}]", path, i), errors);
+        }
+    }
+}
+
+fn full_result() -> ReconResult {
+    ReconResult {
+        target: "shadow".into(),
+        hits: 1,
+        platforms: vec!["github".into()],
+        failed: vec!["reddit: timeout".into()],
+        restricted: vec!["steam".into()],
+        profiles: vec![ProfileHit {
+            provider: "github".into(),
+            url: "https://github.com/shadow".into(),
+            linked: vec![LinkedIdentifier {
+                kind: IdentifierKind::Username,
+                value: "sh4dow".into(),
+                provider: Some("twitter".into()),
+            }],
+            metadata: Some(ProfileMetadata {
+                display_name: Some("Shadow".into()),
+                created_at: Some(Utc::now()),
+                ..Default::default()
+            }),
+        }],
+        provenance: Some(Provenance {
+            parent: "root".into(),
+            provider: "github".into(),
+            identifier: LinkedIdentifier {
+                kind: IdentifierKind::Email,
+                value: "shadow@example.com".into(),
+                provider: None,
+            },
+            depth: 1,
+        }),
+        domain: Some(DomainIntel {
+            registrar: Some("Example Registrar".into()),
+            hostnames: vec!["www.example.com".into()],
+            ..Default::default()
+        }),
+        cached: vec!["github".into()],
+        evidence: vec![EvidenceRef {
+            provider: "github".into(),
+            sha256: "ab".repeat(32),
+            captured_at: Utc::now(),
+        }],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn envelope_matches_published_schema() {
+    let schema: Value = serde_json::from_str(SCHEMA).unwrap();
+    assert_eq!(
+        schema["properties"]["schema_version"]["const"],
+        SCHEMA_VERSION
+    );
+
+    let mut config = AppConfig {
+        providers: vec![
+            ProviderConfig {
+                name: "github".into(),
+                enabled: true,
+                base_url: "https://github.com/{username}".into(),
+            },
+            ProviderConfig {
+                name: "reddit".into(),
+                enabled: false,
+                base_url: "https://www.reddit.com/user/{username}".into(),
+            },
+        ],
+        ..AppConfig::default()
+    };
+    let targets = vec![
+        RequestedTarget {
+            target: "shadow".into(),
+            kind: TargetKind::Username,
+            label: Some("primary".into()),
+            error: None,
+        },
+        RequestedTarget {
+            target: "a/b".into(),
+            kind: TargetKind::Domain,
+            label: None,
+            error: Some("config error: invalid domain `a/b`".into()),
+        },
+    ];
+    let started = Utc::now();
+    let envelope = Envelope::new(&config, targets, true, started, vec![full_result()]);
+    assert_eq!(envelope.providers, vec!["github"]);
+    assert_eq!(envelope.cache.served, 1);
+    assert!(!envelope.cache.disk);
+    assert!(envelope.finished_at >= envelope.started_at);
+    assert_eq!(envelope.tool.version, env!("CARGO_PKG_VERSION"));
+
+    let value = serde_json::to_value(&envelope).unwrap();
+    let mut errors = Vec::new();
+    validate(&schema, &schema, &value, "$", &mut errors);
+    assert!(errors.is_empty(), "{:#?}", errors);
+    // Skipped targets stay listed, with the reason.
+    assert!(value["targets"][0].get("error").is_none());
+    assert_eq!(
+        value["targets"][1]["error"],
+        "config error: invalid domain `a/b`"
+    );
+
+    // The hash follows the effective config.
+    let hash = envelope.config_hash.clone();
+    assert_eq!(config.digest(), hash);
+    config.providers[1].enabled = true;
+    assert_ne!(config.digest(), hash);
+
+    // Credentials never reach the published hash.
+    let hash = config.digest();
+    config.webhook_url = Some("https://hooks.example/T0/secret-path".into());
+    config.splunk = Some(SplunkConfig {
+        url: "https://splunk.example".into(),
+        token: "hec-secret".into(),
+        index: None,
+        sourcetype: "bf".into(),
+    });
+    config.elasticsearch = Some(ElasticsearchConfig {
+        url: "https://es.example".into(),
+        index: "recon".into(),
+        api_key: Some("es-secret".into()),
+    });
+    let json = serde_json::to_string(&config).unwrap();
+    assert!(!json.contains("secret"), "{}", json);
+    assert_ne!(config.digest(), hash);
+    config.splunk.as_mut().unwrap().token = "rotated".into();
+    config.elasticsearch.as_mut().unwrap().api_key = None;
+    let rotated = config.digest();
+    config.webhook_url = None;
+    assert_eq!(config.digest(), rotated);
+}
+
+#[test]
+fn saved_output_parses_in_any_shape() {
+    let result = full_result();
+    let envelope = Envelope::new(
+        &AppConfig::default(),
+        Vec::new(),
+        false,
+        Utc::now(),
+        vec![result.clone(), result.clone()],
+    );
+    let wrapped = serde_json::to_vec(&envelope).unwrap();
+    assert_eq!(parse_results(&wrapped).unwrap().len(), 2);
+
+    let bare = serde_json::to_vec(&result).unwrap();
+    assert_eq!(parse_results(&bare).unwrap()[0].target, "shadow");
+    let many = serde_json::to_vec(std::slice::from_ref(&result)).unwrap();
+    assert_eq!(parse_results(&many).unwrap().len(), 1);
+
+    let mut future: Value = serde_json::from_slice(&wrapped).unwrap();
+    future["schema_version"] = (SCHEMA_VERSION + 1).into();
+    let err = parse_results(&serde_json::to_vec(&future).unwrap()).unwrap_err();
+    assert!(err.contains("newer"), "{}", err);
+    assert!(parse_results(b"{\"nope\": 1}").is_err());
+}
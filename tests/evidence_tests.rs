@@ -0,0 +1,197 @@
+use std::{
+    fs,
+    time::{Duration, SystemTime},
+};
+
+use bloody_falcon::{
+    commands::evidence::{run, EvidenceCommand},
+    config::{AppConfig, ProviderConfig},
+    core::{
+        crypto::KeySource,
+        engine::{Engine, TargetKind},
+        evidence::{Capture, EvidenceVault},
+    },
+};
+use httpmock::prelude::*;
+
+#[tokio::test]
+async fn hits_are_captured_and_verifiable() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/github/shadow");
+        then.status(200)
+            .header("content-type", "text/html")
+            .header("set-cookie", "session=secret")
+            .body("<html>shadow's profile, padded well past the snapshot cap</html>");
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/reddit/shadow");
+        then.status(404);
+    });
+    let provider = |name: &str| ProviderConfig {
+        name: name.into(),
+        enabled: true,
+        base_url: format!("{}/{}/{{username}}", server.base_url(), name),
+    };
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    let cfg = AppConfig {
+        timeout_ms: 2000,
+        providers: vec![provider("github"), provider("reddit")],
+        evidence_enabled: true,
+        evidence_dir: dir.to_string_lossy().into_owned(),
+        evidence_max_body_bytes: 16,
+        ..AppConfig::default()
+    };
+    let engine = Engine::new(cfg.clone()).unwrap();
+
+    let result = engine.scan_username("shadow", false).await.unwrap();
+    assert_eq!(result.evidence.len(), 1, "only the hit is captured");
+    let reference = &result.evidence[0];
+    assert_eq!(reference.provider, "github");
+
+    let vault = EvidenceVault::new(&cfg, None);
+    let record = vault.load(&reference.sha256).unwrap();
+    assert_eq!(record.target, "shadow");
+    assert_eq!(record.status, 200);
+    assert_eq!(
+        record.request_url,
+        format!("{}/github/shadow", server.base_url())
+    );
+    assert_eq!(record.captured_at, reference.captured_at);
+    assert_eq!(record.body().unwrap(), b"<html>shadow's p");
+    assert!(record.body_truncated);
+    let header = |name: &str| {
+        record
+            .headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    };
+    assert_eq!(header("content-type"), Some("text/html"));
+    assert_eq!(header("set-cookie"), Some("[redacted]"));
+
+    // Tampering is caught on read.
+    assert_eq!(vault.verify().unwrap().ok, 1);
+    let object = dir
+        .join("objects")
+        .join(&reference.sha256[..2])
+        .join(format!("{}.json", reference.sha256));
+    let stored = fs::read_to_string(&object).unwrap();
+    fs::write(
+        &object,
+        stored.replace("\"status\": 200", "\"status\": 404"),
+    )
+    .unwrap();
+    assert!(vault.load(&reference.sha256).is_err());
+    assert_eq!(vault.verify().unwrap().bad.len(), 1);
+}
+
+#[tokio::test]
+async fn domain_finds_and_long_pages_are_captured() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/rdap/domain/example.com");
+        then.status(200)
+            .json_body(serde_json::json!({"ldhName": "example.com"}));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/ct");
+        then.status(404);
+    });
+    // Longer than what a check reads, shorter than the snapshot cap.
+    server.mock(|when, then| {
+        when.method(GET).path("/github/shadow");
+        then.status(200).body("x".repeat(600 * 1024));
+    });
+    let tmp = tempfile::tempdir().unwrap();
+    let key_file = tmp.path().join("vault.key");
+    fs::write(&key_file, b"0123456789abcdef0123456789abcdef").unwrap();
+    let cfg = AppConfig {
+        key_file: Some(key_file.to_string_lossy().into_owned()),
+        timeout_ms: 2000,
+        rdap_url: format!("{}/rdap/domain/{{domain}}", server.base_url()),
+        ct_url: format!("{}/ct?q={{domain}}", server.base_url()),
+        providers: vec![ProviderConfig {
+            name: "github".into(),
+            enabled: true,
+            base_url: format!("{}/github/{{username}}", server.base_url()),
+        }],
+        evidence_enabled: true,
+        evidence_dir: tmp.path().join("vault").to_string_lossy().into_owned(),
+        evidence_max_body_bytes: 1024 * 1024,
+        ..AppConfig::default()
+    };
+    let engine = Engine::new(cfg.clone()).unwrap();
+    let vault = EvidenceVault::new(&cfg, Some(KeySource::KeyFile(key_file)));
+
+    let domain = engine
+        .scan_target(TargetKind::Domain, "example.com", false)
+        .await
+        .unwrap();
+    assert_eq!(domain.evidence.len(), 1);
+    assert_eq!(domain.evidence[0].provider, "rdap");
+    let record = vault.load(&domain.evidence[0].sha256).unwrap();
+    assert_eq!(record.target, "example.com");
+    assert!(String::from_utf8(record.body().unwrap())
+        .unwrap()
+        .contains("example.com"));
+
+    let user = engine.scan_username("shadow", false).await.unwrap();
+    let record = vault.load(&user.evidence[0].sha256).unwrap();
+    assert_eq!(record.body().unwrap().len(), 512 * 1024);
+    assert!(record.body_truncated, "capped before it reached the vault");
+
+    // Exported bodies stay sealed under the vault's key.
+    let out = tmp.path().join("page.html");
+    run(
+        &EvidenceCommand::Show {
+            sha256: domain.evidence[0].sha256.clone(),
+            body: Some(out.clone()),
+        },
+        &vault,
+    )
+    .unwrap();
+    assert!(!fs::read_to_string(&out).unwrap().contains("example.com"));
+}
+
+#[test]
+fn prune_applies_retention_and_size_cap() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    let mut cfg = AppConfig {
+        evidence_dir: dir.to_string_lossy().into_owned(),
+        evidence_retention_days: 30,
+        ..AppConfig::default()
+    };
+    let vault = EvidenceVault::new(&cfg, None);
+    let capture = |body: &str| Capture {
+        url: "https://example.com/shadow".into(),
+        final_url: "https://example.com/shadow".into(),
+        status: 200,
+        headers: Vec::new(),
+        body: body.as_bytes().to_vec(),
+        truncated: false,
+    };
+    let old = vault.store("shadow", "github", &capture("old")).unwrap();
+    let kept = vault.store("shadow", "reddit", &capture("new")).unwrap();
+    let path = |hash: &str| {
+        dir.join("objects")
+            .join(&hash[..2])
+            .join(format!("{}.json", hash))
+    };
+    fs::File::options()
+        .write(true)
+        .open(path(&old.sha256))
+        .unwrap()
+        .set_modified(SystemTime::now() - Duration::from_secs(31 * 86_400))
+        .unwrap();
+
+    assert_eq!(vault.prune().unwrap(), 1);
+    assert_eq!(vault.list().unwrap(), vec![kept.sha256.clone()]);
+
+    // A zero size cap empties the vault regardless of age.
+    cfg.evidence_max_total_mb = 0;
+    assert_eq!(EvidenceVault::new(&cfg, None).prune().unwrap(), 1);
+    assert!(vault.list().unwrap().is_empty());
+}
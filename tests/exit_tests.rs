@@ -0,0 +1,63 @@
+use bloody_falcon::core::{
+    engine::ReconResult,
+    exit::{classify, ExitStatus, FailOn},
+};
+
+fn result(hits: usize, failed: &[&str], restricted: &[&str]) -> ReconResult {
+    ReconResult {
+        target: "shadow".into(),
+        hits,
+        failed: failed.iter().map(|s| s.to_string()).collect(),
+        restricted: restricted.iter().map(|s| s.to_string()).collect(),
+        ..Default::default()
+    }
+}
+
+fn one(result: &ReconResult) -> &[ReconResult] {
+    std::slice::from_ref(result)
+}
+
+#[test]
+fn exit_status_reflects_outcome_and_policy() {
+    let hit = result(1, &[], &[]);
+    let miss = result(0, &[], &[]);
+    let degraded = result(1, &["reddit: timeout"], &["steam"]);
+
+    assert_eq!(classify(one(&hit), 0, &[]), ExitStatus::Hits);
+    assert_eq!(classify(one(&miss), 0, &[]), ExitStatus::NoHits);
+    assert_eq!(classify(&[miss, hit.clone()], 0, &[]), ExitStatus::Hits);
+
+    // Failed or rate-limited checks always make the run partial, even
+    // when every provider timed out; restricted ones count as completed.
+    let timed_out = result(0, &["github: timeout", "reddit: timeout"], &[]);
+    assert_eq!(classify(one(&timed_out), 0, &[]), ExitStatus::Partial);
+    assert_eq!(classify(one(&degraded), 0, &[]), ExitStatus::Partial);
+    let mut limited = hit.clone();
+    limited.rate_limited = vec!["steam".into()];
+    assert_eq!(classify(one(&limited), 0, &[]), ExitStatus::Partial);
+    let blocked = result(1, &[], &["steam"]);
+    assert_eq!(classify(one(&blocked), 0, &[]), ExitStatus::Hits);
+
+    // `--fail-on` escalates matching outcomes to a fatal run.
+    assert_eq!(
+        classify(one(&blocked), 0, &[FailOn::Restricted]),
+        ExitStatus::Fatal
+    );
+    assert_eq!(
+        classify(one(&degraded), 0, &[FailOn::RateLimited]),
+        ExitStatus::Partial
+    );
+    assert_eq!(
+        classify(
+            &[hit.clone(), degraded],
+            0,
+            &[FailOn::RateLimited, FailOn::Failed]
+        ),
+        ExitStatus::Fatal
+    );
+
+    // A batch target that could not be scanned at all is always partial.
+    assert_eq!(classify(&[hit], 1, &[]), ExitStatus::Partial);
+    assert_eq!(classify(&[], 1, &[]), ExitStatus::Partial);
+    assert_eq!(ExitStatus::Fatal as u8, 2);
+}
@@ -0,0 +1,311 @@
+use bloody_falcon::core::engine::{ProfileHit, ReconResult, TargetKind};
+use bloody_falcon::export::graph::{to_dot, to_gexf, to_graphml, Graph};
+use bloody_falcon::export::html::{to_html, ReportMeta, TEMPLATE};
+use bloody_falcon::export::notes::{write_notes, NOTES_MARKER};
+use bloody_falcon::export::stix::to_bundle;
+use bloody_falcon::export::{render, Format};
+use bloody_falcon::modules::crosslink::{pivot::Provenance, IdentifierKind, LinkedIdentifier};
+use bloody_falcon::modules::recon::metadata::ProfileMetadata;
+use chrono::Utc;
+
+fn sample() -> ReconResult {
+    ReconResult {
+        target: "shadow".into(),
+        hits: 1,
+        platforms: vec!["github".into()],
+        profiles: vec![ProfileHit {
+            provider: "github".into(),
+            url: "https://github.com/shadow".into(),
+            linked: vec![
+                LinkedIdentifier {
+                    kind: IdentifierKind::Username,
+                    value: "sh4dow".into(),
+                    provider: Some("twitter".into()),
+                },
+                LinkedIdentifier {
+                    kind: IdentifierKind::Email,
+                    value: "shadow@example.com".into(),
+                    provider: None,
+                },
+            ],
+            metadata: None,
+        }],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn graph_has_typed_nodes_and_edges() {
+    let graph = Graph::from_results(&[sample(), sample()]);
+    let kinds: Vec<&str> = graph.nodes.iter().map(|n| n.kind.as_str()).collect();
+    assert_eq!(
+        kinds,
+        vec!["target", "provider", "profile", "username", "provider", "email"]
+    );
+    // target->profile, profile->provider, username->twitter, profile->username, profile->email
+    assert_eq!(graph.edges.len(), 5);
+}
+
+#[test]
+fn exporters_render_graph() {
+    let results = [sample()];
+    let graphml = to_graphml(&results);
+    assert!(graphml.contains("<graphml"));
+    assert!(graphml.contains("<data key=\"relation\">links_to</data>"));
+
+    let gexf = to_gexf(&results);
+    assert!(gexf.contains("version=\"1.3\""));
+    assert!(gexf.contains("label=\"shadow@example.com\""));
+
+    let dot = to_dot(&results);
+    assert!(dot.starts_with("digraph bloody_falcon {"));
+    assert!(dot.contains("\"target:shadow\" -> \"profile:https://github.com/shadow\""));
+}
+
+#[test]
+fn row_formats_list_each_provider() {
+    let mut blocked = sample();
+    blocked.restricted = vec!["steam".into()];
+    blocked.failed = vec!["reddit: timeout".into()];
+    blocked.cached = vec!["github".into()];
+    let empty = ReconResult {
+        target: "nobody, really".into(),
+        ..Default::default()
+    };
+    let results = [blocked, empty];
+
+    let csv = render(Format::Csv, &results).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "target,kind,provider,status,url,cached,detail",
+            "shadow,username,github,hit,https://github.com/shadow,true,",
+            "shadow,username,reddit,failed,,false,timeout",
+            "shadow,username,steam,restricted,,false,",
+            "\"nobody, really\",username,,none,,false,",
+        ]
+    );
+
+    let jsonl = render(Format::Jsonl, &results).unwrap();
+    assert_eq!(jsonl.lines().count(), 2);
+    let first: ReconResult = serde_json::from_str(jsonl.lines().next().unwrap()).unwrap();
+    assert_eq!(first.platforms, vec!["github"]);
+
+    let table = render(Format::Table, &results).unwrap();
+    let mut rows = table.lines();
+    let header = rows.next().unwrap();
+    let github = rows.next().unwrap();
+    assert!(header.starts_with("TARGET          KIND      PROVIDER  STATUS"));
+    assert_eq!(header.find("URL"), github.find("https://"));
+    assert!(github.contains("hit (cached)"));
+    assert!(table.contains("failed (timeout)"));
+    assert!(table.ends_with("2 targets, 1 hit\n"));
+
+    let markdown = render(Format::Markdown, &results).unwrap();
+    assert!(markdown.contains("## shadow (username)"));
+    assert!(markdown.contains("| github | hit (cached) | <https://github.com/shadow> |  |"));
+    assert!(markdown.contains("| reddit | failed |  | timeout |"));
+    assert!(markdown.contains("_No accounts found._"));
+}
+
+#[test]
+fn html_report_escapes_and_uses_template() {
+    let mut result = sample();
+    result.target = "<script>{{targets}}</script>".into();
+    result.restricted = vec!["steam".into()];
+    result.failed = vec!["reddit: timeout".into()];
+    result.profiles[0].url = "javascript:alert(1)".into();
+    let mut other = sample();
+    other.profiles[0].url = "https://github.com/shadow?a=1&b=2".into();
+
+    let report = render(Format::Html, &[result.clone(), other]).unwrap();
+    assert!(report.starts_with("<!DOCTYPE html>"));
+    assert!(report.contains("<style>"));
+    assert!(!report.contains("<link") && !report.contains("<script"));
+    assert!(report.contains("&lt;script&gt;{{targets}}&lt;/script&gt;"));
+    assert!(report.contains("href=\"https://github.com/shadow?a=1&amp;b=2\""));
+    assert!(!report.contains("href=\"javascript:"));
+    assert!(report.contains("<h3 class=\"restricted\">Restricted</h3>"));
+    assert!(report.contains("reddit — <span class=\"muted\">timeout</span>"));
+    assert!(report.contains("<a href=\"#target-2\">shadow</a>"));
+
+    let meta = ReportMeta {
+        title: "Case nightjar".into(),
+        providers: vec!["github".into(), "reddit".into()],
+        ..ReportMeta::default()
+    };
+    let custom = to_html(
+        &[result],
+        "<h1>{{ title }}</h1>{{summary}}{{unknown}}",
+        &meta,
+    );
+    assert!(custom.starts_with("<h1>Case nightjar</h1><section id=\"summary\">"));
+    assert!(custom.ends_with("{{unknown}}"));
+    assert!(to_html(&[], TEMPLATE, &meta).contains("<dd>github, reddit</dd>"));
+}
+
+#[test]
+fn stix_bundle_maps_targets_hits_and_provenance() {
+    let root = sample();
+    let child = ReconResult {
+        target: "sh4dow".into(),
+        hits: 1,
+        platforms: vec!["twitter".into()],
+        profiles: vec![ProfileHit {
+            provider: "twitter".into(),
+            url: "https://twitter.com/sh4dow".into(),
+            linked: vec![],
+            metadata: Some(ProfileMetadata {
+                created_at: Some("2019-04-01T12:00:00Z".parse().unwrap()),
+                ..Default::default()
+            }),
+        }],
+        provenance: Some(Provenance {
+            parent: "shadow".into(),
+            provider: "github".into(),
+            identifier: root.profiles[0].linked[0].clone(),
+            depth: 1,
+        }),
+        ..Default::default()
+    };
+    let domain = ReconResult {
+        target: "example.com".into(),
+        kind: TargetKind::Domain,
+        profiles: vec![ProfileHit {
+            provider: "ct".into(),
+            url: "https://crt.sh/?q=example.com".into(),
+            linked: vec![LinkedIdentifier {
+                kind: IdentifierKind::Domain,
+                value: "www.example.com".into(),
+                provider: None,
+            }],
+            metadata: None,
+        }],
+        ..Default::default()
+    };
+
+    let bundle = to_bundle(&[root, child, domain], Utc::now());
+    assert_eq!(bundle["type"], "bundle");
+    assert!(bundle["id"].as_str().unwrap().starts_with("bundle--"));
+    let objects = bundle["objects"].as_array().unwrap();
+    let ids: Vec<&str> = objects.iter().map(|o| o["id"].as_str().unwrap()).collect();
+    for object in objects {
+        let kind = object["type"].as_str().unwrap();
+        assert!(object["id"]
+            .as_str()
+            .unwrap()
+            .starts_with(&format!("{}--", kind)));
+        assert_eq!(object["spec_version"], "2.1");
+        if matches!(kind, "identity" | "relationship") {
+            let created = object["created"].as_str().unwrap();
+            assert!(created.ends_with('Z') && created.len() == 24, "{}", created);
+            assert_eq!(object["created"], object["modified"]);
+        }
+        if kind == "relationship" {
+            assert!(ids.contains(&object["source_ref"].as_str().unwrap()));
+            assert!(ids.contains(&object["target_ref"].as_str().unwrap()));
+        }
+    }
+    let find = |id: &str| objects.iter().find(|o| o["id"] == id).unwrap();
+
+    // Deterministic observable ids (the domain one is the spec's own example).
+    let account = find("user-account--78184545-6cbf-5738-83a1-92febdb8bb65");
+    assert_eq!(account["account_login"], "shadow");
+    assert_eq!(account["account_type"], "github");
+    assert_eq!(
+        account["x_bloody_falcon_profile_url"],
+        "https://github.com/shadow"
+    );
+    assert_eq!(
+        find("domain-name--bedb4899-d24b-5401-bc86-8f6b4cc18ec7")["value"],
+        "example.com"
+    );
+
+    // Linked from shadow's profile and scanned as a pivot: one merged account.
+    let twitter: Vec<_> = objects
+        .iter()
+        .filter(|o| o["account_login"] == "sh4dow")
+        .collect();
+    assert_eq!(twitter.len(), 1);
+    assert_eq!(twitter[0]["account_created"], "2019-04-01T12:00:00.000Z");
+    assert!(objects
+        .iter()
+        .any(|o| o["type"] == "email-addr" && o["value"] == "shadow@example.com"));
+
+    let pivot = objects
+        .iter()
+        .find(|o| o["x_bloody_falcon_pivot_depth"] == 1)
+        .unwrap();
+    assert_eq!(
+        find(pivot["source_ref"].as_str().unwrap())["name"],
+        "sh4dow"
+    );
+    assert_eq!(
+        find(pivot["target_ref"].as_str().unwrap())["name"],
+        "shadow"
+    );
+    assert_eq!(pivot["relationship_type"], "related-to");
+
+    let rendered = render(Format::Stix, &[sample()]).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+    assert_eq!(parsed["type"], "bundle");
+}
+
+#[test]
+fn notes_update_in_place_and_keep_user_notes() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path().join("notes");
+    let labels = [("shadow".to_string(), "primary".to_string())].into();
+    let mut blocked = sample();
+    blocked.restricted = vec!["steam".into()];
+
+    let written = write_notes(&[blocked.clone()], &labels, &dir, Utc::now()).unwrap();
+    assert_eq!(written, vec![dir.join("shadow.md")]);
+    let note = std::fs::read_to_string(&written[0]).unwrap();
+    assert!(note.starts_with("---\ntarget: \"shadow\"\nkind: username\nlabel: \"primary\"\n"));
+    assert!(note.contains("\nhits: 1\n"));
+    assert!(note.contains("| github | hit | [github](https://github.com/shadow) |"));
+    assert!(note.contains("| steam | restricted |  |"));
+    assert!(note.contains("- [[sh4dow]] (twitter username, from github)"));
+    assert!(note.contains("- [[shadow@example.com]] (email, from github)"));
+
+    // The analyst's notes survive a re-export; the generated part is replaced.
+    let edited = format!("{}Seen in forum dump 2024-03.\n", note);
+    std::fs::write(&written[0], edited).unwrap();
+    write_notes(&[sample()], &labels, &dir, Utc::now()).unwrap();
+    let note = std::fs::read_to_string(&written[0]).unwrap();
+    assert!(!note.contains("steam"));
+    assert_eq!(note.matches(NOTES_MARKER).count(), 1);
+    assert!(note.ends_with(&format!(
+        "{}\n\nSeen in forum dump 2024-03.\n",
+        NOTES_MARKER
+    )));
+
+    // Unsafe characters are kept out of file names and links.
+    let mut odd = sample();
+    odd.target = "a/b".into();
+    odd.profiles[0].linked.truncate(1);
+    odd.profiles[0].linked[0].value = "x|y".into();
+    let written = write_notes(&[odd.clone()], &labels, &dir, Utc::now()).unwrap();
+    assert_eq!(written, vec![dir.join("a_b.md")]);
+    assert!(std::fs::read_to_string(&written[0])
+        .unwrap()
+        .contains("[[x_y|xy]]"));
+
+    // A different target mapping to the same name gets its own note, in the
+    // same run or a later one, and re-exports find it again.
+    let mut clash = sample();
+    clash.target = "a_b".into();
+    let first = write_notes(&[clash.clone(), odd.clone()], &labels, &dir, Utc::now()).unwrap();
+    assert_eq!(first[1], dir.join("a_b.md"));
+    assert_ne!(first[0], first[1]);
+    assert!(std::fs::read_to_string(&first[0])
+        .unwrap()
+        .starts_with("---\ntarget: \"a_b\"\n"));
+    assert!(std::fs::read_to_string(&first[1])
+        .unwrap()
+        .starts_with("---\ntarget: \"a/b\"\n"));
+    let again = write_notes(&[odd, clash], &labels, &dir, Utc::now()).unwrap();
+    assert_eq!(again, vec![first[1].clone(), first[0].clone()]);
+}
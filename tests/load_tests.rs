@@ -0,0 +1,71 @@
+use bloody_falcon::{
+    config::AppConfig,
+    core::engine::{ReconResult, TargetKind},
+    export::envelope::{parse_results, Envelope},
+    ui::app::{App, Status},
+};
+use chrono::{Duration, Utc};
+
+fn scan(target: &str, platforms: &[&str]) -> ReconResult {
+    ReconResult {
+        target: target.into(),
+        hits: platforms.len(),
+        platforms: platforms.iter().map(|p| p.to_string()).collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn loaded_results_become_rescannable_targets() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("results.json");
+    let yesterday = Utc::now() - Duration::days(1);
+    let mut envelope = Envelope::new(
+        &AppConfig::default(),
+        Vec::new(),
+        true,
+        yesterday,
+        vec![scan("shadow", &["github", "reddit"]), scan("ghost", &[])],
+    );
+    envelope.finished_at = yesterday;
+    std::fs::write(&path, serde_json::to_vec(&envelope).unwrap()).unwrap();
+
+    let mut app = App::new();
+    app.add_target("shadow".into());
+    assert_eq!(app.load_saved(&path, None).unwrap(), 2);
+    assert_eq!(app.targets.len(), 2, "known targets are not duplicated");
+    let shadow = &app.targets[0];
+    assert_eq!(shadow.status, Status::Loaded);
+    assert_eq!(shadow.scanned_at, Some(yesterday));
+    assert_eq!(shadow.platforms, vec!["github", "reddit"]);
+    assert_eq!(app.targets[1].id, "ghost");
+
+    // A rescan is diffed against the loaded baseline.
+    let (idx, kind, id) = app.start_scan().unwrap();
+    assert_eq!((kind, id.as_str()), (TargetKind::Username, "shadow"));
+    app.complete_scan(idx, scan("shadow", &["github"]));
+    let shadow = &app.targets[0];
+    assert_eq!(shadow.status, Status::Found);
+    assert_eq!(shadow.previous.as_ref().unwrap().platforms.len(), 2);
+    assert_eq!(shadow.previous_at, Some(yesterday));
+
+    assert!(app
+        .load_saved(&path.with_extension("missing"), None)
+        .is_err());
+}
+
+#[test]
+fn json_lines_output_parses() {
+    let lines = [scan("shadow", &["github"]), scan("ghost", &[])]
+        .iter()
+        .map(|r| serde_json::to_string(r).unwrap())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let results = parse_results(lines.as_bytes()).unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[1].target, "ghost");
+
+    let broken = format!("{}\n{{\"target\": 1}}\n", lines);
+    let err = parse_results(broken.as_bytes()).unwrap_err();
+    assert!(err.starts_with("line 3"), "{}", err);
+}
@@ -0,0 +1,37 @@
+use bloody_falcon::{core::logger::LogFeed, ui::app::App};
+use tracing_subscriber::{fmt, layer::SubscriberExt};
+
+#[test]
+fn tracing_events_reach_the_log_panel() {
+    let feed = LogFeed::new();
+    let subscriber = tracing_subscriber::registry().with(
+        fmt::layer()
+            .with_writer(feed.clone())
+            .with_ansi(false)
+            .with_target(false)
+            .without_time(),
+    );
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!("disk cache opened");
+        tracing::warn!("pivot scan of {} failed", "sh4dow");
+    });
+
+    let mut app = App::new();
+    app.attach_log_feed(feed.clone());
+    app.pull_logs();
+    let tail: Vec<&str> = app
+        .logs
+        .iter()
+        .rev()
+        .take(2)
+        .map(|line| line.split_once("] ").unwrap().1)
+        .collect();
+    assert_eq!(
+        tail,
+        vec![
+            "[WARN] pivot scan of sh4dow failed",
+            "[INFO] disk cache opened"
+        ]
+    );
+    assert!(feed.drain().is_empty(), "lines are handed over once");
+}
@@ -0,0 +1,155 @@
+use bloody_falcon::{
+    config::{AppConfig, ElasticsearchConfig, SplunkConfig},
+    core::engine::ReconResult,
+    export::siem::{bulk_body, hec_body, DeliveryReport, SiemSinks},
+};
+use chrono::{TimeZone, Utc};
+use httpmock::prelude::*;
+use serde_json::Value;
+
+fn results() -> Vec<ReconResult> {
+    ["shadow", "ghost", "sh4dow"]
+        .iter()
+        .map(|target| ReconResult {
+            target: target.to_string(),
+            hits: 1,
+            platforms: vec!["github".into()],
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn splunk(url: String) -> SplunkConfig {
+    SplunkConfig {
+        url,
+        token: "hec-token".into(),
+        index: Some("osint".into()),
+        sourcetype: "bloody_f4lcon:result".into(),
+    }
+}
+
+#[test]
+fn payloads_follow_bulk_and_hec_formats() {
+    let at = Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+    let results = results();
+
+    let bulk = bulk_body(&results[..1], "recon", at);
+    let lines: Vec<Value> = bulk
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert!(bulk.ends_with('\n'), "_bulk needs a trailing newline");
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["index"]["_index"], "recon");
+    assert_eq!(lines[1]["target"], "shadow");
+    assert_eq!(lines[1]["@timestamp"], "2024-03-01T12:00:00.000Z");
+    assert_eq!(lines[1]["tool"]["version"], env!("CARGO_PKG_VERSION"));
+
+    let hec = hec_body(&results, &splunk("http://unused".into()), at);
+    let events: Vec<Value> = hec
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(events.len(), 3);
+    assert_eq!(events[0]["time"], 1709294400.0);
+    assert_eq!(events[0]["index"], "osint");
+    assert_eq!(events[0]["sourcetype"], "bloody_f4lcon:result");
+    assert_eq!(events[2]["event"]["target"], "sh4dow");
+}
+
+#[tokio::test]
+async fn batches_are_delivered_and_failures_retried_without_error() {
+    let server = MockServer::start();
+    let bulk = server.mock(|when, then| {
+        when.method(POST)
+            .path("/es/_bulk")
+            .header("authorization", "ApiKey es-key")
+            .header("content-type", "application/x-ndjson")
+            .body_contains("{\"index\":{\"_index\":\"recon\"}}");
+        then.status(200)
+            .json_body(serde_json::json!({ "errors": false, "items": [] }));
+    });
+    let hec = server.mock(|when, then| {
+        when.method(POST)
+            .path("/services/collector/event")
+            .header("authorization", "Splunk hec-token");
+        then.status(503);
+    });
+
+    let cfg = AppConfig {
+        timeout_ms: 2000,
+        elasticsearch: Some(ElasticsearchConfig {
+            url: server.url("/es/"),
+            index: "recon".into(),
+            api_key: Some("es-key".into()),
+        }),
+        splunk: Some(splunk(server.base_url())),
+        siem_batch_size: 2,
+        siem_retries: 1,
+        ..AppConfig::default()
+    };
+    assert!(SiemSinks::from_config(&AppConfig::default()).is_none());
+    let sinks = SiemSinks::from_config(&cfg).unwrap();
+
+    let report = sinks.send(&results(), Utc::now()).await;
+    assert_eq!(
+        report.elasticsearch,
+        Some(DeliveryReport { sent: 3, failed: 0 })
+    );
+    bulk.assert_hits(2);
+    // Two batches, each tried twice, then given up on.
+    assert_eq!(report.splunk, Some(DeliveryReport { sent: 0, failed: 3 }));
+    hec.assert_hits(4);
+}
+
+#[tokio::test]
+async fn bulk_items_are_checked_and_pushed_back_ones_retried() {
+    let server = MockServer::start();
+    // The whole batch: one indexed, one rejected, one pushed back.
+    let partial = server.mock(|when, then| {
+        when.method(POST).path("/_bulk").body_contains("\"ghost\"");
+        then.status(200).json_body(serde_json::json!({
+            "errors": true,
+            "items": [
+                {"index": {"status": 201}},
+                {"index": {"status": 400, "error": {"type": "mapper_parsing_exception"}}},
+                {"index": {"status": 429, "error": {"type": "es_rejected_execution_exception"}}}
+            ]
+        }));
+    });
+    // The retry carries only the pushed-back document.
+    let retry = server.mock(|when, then| {
+        when.method(POST)
+            .path("/_bulk")
+            .body_contains("\"sh4dow\"")
+            .matches(|req| {
+                let body = req.body.as_deref().unwrap_or_default();
+                !String::from_utf8_lossy(body).contains("\"ghost\"")
+            });
+        then.status(200).json_body(serde_json::json!({
+            "errors": false,
+            "items": [{"index": {"status": 201}}]
+        }));
+    });
+
+    let cfg = AppConfig {
+        timeout_ms: 2000,
+        elasticsearch: Some(ElasticsearchConfig {
+            url: server.base_url(),
+            index: "recon".into(),
+            api_key: None,
+        }),
+        siem_batch_size: 3,
+        siem_retries: u32::MAX,
+        ..AppConfig::default()
+    };
+    let sinks = SiemSinks::from_config(&cfg).unwrap();
+
+    let report = sinks.send(&results(), Utc::now()).await;
+    assert_eq!(
+        report.elasticsearch,
+        Some(DeliveryReport { sent: 2, failed: 1 })
+    );
+    partial.assert_hits(1);
+    retry.assert_hits(1);
+}
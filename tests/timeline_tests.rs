@@ -0,0 +1,86 @@
+use bloody_falcon::core::engine::{ProfileHit, ReconResult};
+use bloody_falcon::export::timeline::to_csv;
+use bloody_falcon::modules::recon::metadata::{extract_metadata, ProfileMetadata};
+use bloody_falcon::modules::timeline::{Activity, ActivityWindow, Timeline};
+use chrono::{TimeZone, Utc};
+
+fn hit(provider: &str, created: Option<&str>, active: Option<&str>) -> ProfileHit {
+    let parse = |s: &str| chrono::DateTime::parse_from_rfc3339(s).unwrap().into();
+    ProfileHit {
+        provider: provider.into(),
+        url: format!("https://{}.example/shadow", provider),
+        linked: vec![],
+        metadata: Some(ProfileMetadata {
+            created_at: created.map(parse),
+            last_active: active.map(parse),
+            ..Default::default()
+        }),
+    }
+}
+
+#[test]
+fn timestamps_from_profile_json() {
+    let meta = extract_metadata(
+        r#"{"login":"shadow","created_at":"2014-05-01T10:00:00Z","lastSeen":1700000000}"#,
+        "github",
+    )
+    .unwrap();
+    assert_eq!(
+        meta.created_at,
+        Some(Utc.with_ymd_and_hms(2014, 5, 1, 10, 0, 0).unwrap())
+    );
+    assert_eq!(
+        meta.last_active,
+        Utc.timestamp_opt(1_700_000_000, 0).single()
+    );
+}
+
+#[test]
+fn timeline_orders_accounts_and_flags_activity() {
+    let result = ReconResult {
+        target: "shadow".into(),
+        profiles: vec![
+            hit(
+                "reddit",
+                Some("2018-01-01T00:00:00Z"),
+                Some("2026-10-10T00:00:00Z"),
+            ),
+            hit(
+                "github",
+                Some("2012-06-01T00:00:00Z"),
+                Some("2020-01-01T00:00:00Z"),
+            ),
+            hit("steam", None, None),
+        ],
+        ..Default::default()
+    };
+    let now = Utc.with_ymd_and_hms(2026, 10, 18, 0, 0, 0).unwrap();
+    let timeline = Timeline::build(&result, now, ActivityWindow::default());
+
+    assert_eq!(
+        timeline.established,
+        Some(Utc.with_ymd_and_hms(2012, 6, 1, 0, 0, 0).unwrap())
+    );
+    let order: Vec<(&str, Option<usize>, Activity)> = timeline
+        .accounts
+        .iter()
+        .map(|a| (a.provider.as_str(), a.creation_rank, a.activity))
+        .collect();
+    assert_eq!(
+        order,
+        vec![
+            ("github", Some(1), Activity::Dormant),
+            ("reddit", Some(2), Activity::Recent),
+            ("steam", None, Activity::Unknown),
+        ]
+    );
+    assert_eq!(timeline.events.len(), 4);
+
+    let csv = to_csv(&[result]);
+    let mut rows = csv.lines();
+    assert_eq!(rows.next(), Some("target,at,provider,event,activity,url"));
+    assert_eq!(
+        rows.next(),
+        Some("shadow,2012-06-01T00:00:00Z,github,created,dormant,https://github.example/shadow")
+    );
+}
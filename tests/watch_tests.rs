@@ -0,0 +1,193 @@
+use std::time::Duration;
+
+use bloody_falcon::{
+    config::{AppConfig, ProviderConfig},
+    core::engine::{Engine, ReconResult, TargetKind},
+    modules::watch::{alerts_for, AlertSinks, Schedule, Watcher},
+};
+use chrono::{DateTime, TimeZone, Utc};
+use httpmock::prelude::*;
+
+#[test]
+fn schedules_compute_next_run() {
+    let last = Utc.with_ymd_and_hms(2024, 3, 1, 10, 17, 0).unwrap();
+    let every = Schedule::Every(Duration::from_secs(30 * 60));
+    assert_eq!(
+        every.next_after(last),
+        Utc.with_ymd_and_hms(2024, 3, 1, 10, 47, 0).unwrap()
+    );
+
+    let cron = Schedule::cron("0 */6 * * *").unwrap();
+    assert_eq!(
+        cron.next_after(last),
+        Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap()
+    );
+    assert!(Schedule::cron("not a schedule").is_err());
+
+    // An interval past the calendar means "never again", not a panic.
+    assert_eq!(
+        Schedule::Every(Duration::from_secs(u64::MAX)).next_after(last),
+        DateTime::<Utc>::MAX_UTC
+    );
+    assert_eq!(
+        Schedule::Every(Duration::from_secs(300_000 * 365 * 86_400)).next_after(last),
+        DateTime::<Utc>::MAX_UTC
+    );
+}
+
+#[test]
+fn a_failed_check_between_hits_is_not_an_alert() {
+    let scan = |platforms: &[&str], failed: &[&str]| ReconResult {
+        target: "shadow".into(),
+        hits: platforms.len(),
+        platforms: platforms.iter().map(|p| p.to_string()).collect(),
+        failed: failed.iter().map(|p| format!("{}: timeout", p)).collect(),
+        ..Default::default()
+    };
+    let hit = scan(&["github"], &[]);
+    let timed_out = scan(&[], &["github"]);
+    let now = Utc::now();
+    assert!(alerts_for(&hit, &timed_out, now).unwrap().is_empty());
+    assert!(alerts_for(&timed_out, &hit, now).unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn watcher_alerts_on_changes_and_resumes_schedule() {
+    let server = MockServer::start();
+    let mut github = server.mock(|when, then| {
+        when.method(GET).path("/github/shadow");
+        then.status(200);
+    });
+    let mut reddit = server.mock(|when, then| {
+        when.method(GET).path("/reddit/shadow");
+        then.status(404);
+    });
+    let webhook = server.mock(|when, then| {
+        when.method(POST).path("/alerts");
+        then.status(204);
+    });
+
+    let provider = |name: &str| ProviderConfig {
+        name: name.into(),
+        enabled: true,
+        base_url: format!("{}/{}/{{username}}", server.base_url(), name),
+    };
+    let cfg = AppConfig {
+        timeout_ms: 2000,
+        providers: vec![provider("github"), provider("reddit")],
+        ..AppConfig::default()
+    };
+    let engine = Engine::new(cfg).unwrap();
+
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    let state_path = dir.join("watch.json");
+    let alert_log = dir.join("alerts.jsonl");
+    let sinks = AlertSinks {
+        stdout: false,
+        log: Some(alert_log.clone()),
+        webhook: Some(server.url("/alerts")),
+    };
+    let schedule = Schedule::Every(Duration::from_secs(3600));
+    let targets = vec![(TargetKind::Username, "shadow".to_string())];
+
+    let t0 = Utc.with_ymd_and_hms(2024, 3, 1, 10, 0, 0).unwrap();
+    let mut watcher = Watcher::new(
+        schedule.clone(),
+        targets.clone(),
+        sinks.clone(),
+        state_path.clone(),
+        None,
+    )
+    .unwrap();
+    assert_eq!(watcher.next_due(), Some(chrono::DateTime::<Utc>::MIN_UTC));
+    assert!(watcher.tick(&engine, t0).await.unwrap().is_empty());
+
+    // A restarted watcher picks the schedule up where it left off.
+    let mut watcher = Watcher::new(schedule, targets, sinks, state_path.clone(), None).unwrap();
+    let due = t0 + chrono::Duration::hours(1);
+    assert_eq!(watcher.next_due(), Some(due));
+    assert!(watcher
+        .tick(&engine, t0 + chrono::Duration::minutes(30))
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(github.hits(), 1);
+
+    github.delete();
+    reddit.delete();
+    github = server.mock(|when, then| {
+        when.method(GET).path("/github/shadow");
+        then.status(404);
+    });
+    reddit = server.mock(|when, then| {
+        when.method(GET).path("/reddit/shadow");
+        then.status(200);
+    });
+
+    let alerts = watcher.tick(&engine, due).await.unwrap();
+    let changes: Vec<(&str, &str)> = alerts
+        .iter()
+        .map(|a| (a.provider.as_str(), a.change.as_str()))
+        .collect();
+    assert_eq!(
+        changes,
+        vec![("github", "disappeared"), ("reddit", "appeared")]
+    );
+    assert_eq!(github.hits() + reddit.hits(), 2);
+    webhook.assert_hits(2);
+
+    let logged = std::fs::read_to_string(&alert_log).unwrap();
+    assert_eq!(logged.lines().count(), 2);
+    assert!(logged.contains(r#""change":"appeared""#));
+    assert_eq!(watcher.next_due(), Some(due + chrono::Duration::hours(1)));
+}
+
+#[tokio::test]
+async fn a_miss_after_a_failed_check_still_alerts() {
+    let server = MockServer::start();
+    let engine = Engine::new(AppConfig {
+        timeout_ms: 2000,
+        providers: vec![ProviderConfig {
+            name: "github".into(),
+            enabled: true,
+            base_url: format!("{}/github/{{username}}", server.base_url()),
+        }],
+        ..AppConfig::default()
+    })
+    .unwrap();
+    let tmp = tempfile::tempdir().unwrap();
+    let sinks = AlertSinks {
+        stdout: false,
+        log: None,
+        webhook: None,
+    };
+    let mut watcher = Watcher::new(
+        Schedule::Every(Duration::from_secs(3600)),
+        vec![(TargetKind::Username, "shadow".to_string())],
+        sinks,
+        tmp.path().join("watch.json"),
+        None,
+    )
+    .unwrap();
+
+    let t0 = Utc.with_ymd_and_hms(2024, 3, 1, 10, 0, 0).unwrap();
+    let mut alerts = Vec::new();
+    for (hour, status) in [(0, 200), (1, 503), (2, 404)] {
+        let mut github = server.mock(|when, then| {
+            when.method(GET).path("/github/shadow");
+            then.status(status);
+        });
+        let at = t0 + chrono::Duration::hours(hour);
+        alerts.push(watcher.tick(&engine, at).await.unwrap());
+        github.assert_hits(1);
+        github.delete();
+    }
+    assert!(alerts[0].is_empty());
+    assert!(alerts[1].is_empty());
+    let changes: Vec<(&str, &str)> = alerts[2]
+        .iter()
+        .map(|a| (a.provider.as_str(), a.change.as_str()))
+        .collect();
+    assert_eq!(changes, vec![("github", "disappeared")]);
+}